@@ -1,9 +1,13 @@
 extern crate test;
 
-use crate::main;
+use crate::cli::Args;
+use crate::render_scene;
+use clap::Parser;
 use test::Bencher;
 
 #[bench]
 fn bench_render(bencher: &mut Bencher) {
-  bencher.iter(|| main());
+  let args = Args::parse_from(["ray-tracing"]);
+
+  bencher.iter(|| render_scene(&args));
 }
@@ -0,0 +1,142 @@
+use crate::image::exr::{ExrCompression, ExrOptions, ExrPixelType};
+use crate::renderer::integrators::integrator::IntegratorKind;
+use crate::renderer::samplers::sampler::SamplerKind;
+use clap::Parser;
+use std::path::PathBuf;
+
+pub const DEFAULT_SCENE: &str = "scenes/default.toml";
+
+fn at_least<const MIN: usize>(value: &str) -> Result<usize, String> {
+  let value = value
+    .parse::<usize>()
+    .map_err(|_| format!("`{value}` is not a whole number"))?;
+
+  if value >= MIN {
+    Ok(value)
+  } else {
+    Err(format!("must be at least {MIN}"))
+  }
+}
+
+fn positive(value: &str) -> Result<f64, String> {
+  let value = value
+    .parse::<f64>()
+    .map_err(|_| format!("`{value}` is not a number"))?;
+
+  if value > 0.0 {
+    Ok(value)
+  } else {
+    Err("must be positive".to_owned())
+  }
+}
+
+/// Renders a scene description with a path tracer. Options override the
+/// settings in the scene's `[render]` table.
+#[derive(Parser)]
+#[command(about)]
+pub struct Args {
+  /// Scene description to render.
+  #[arg(long, default_value = DEFAULT_SCENE)]
+  pub scene: PathBuf,
+
+  /// File to write the image to, as a PNG, binary PPM, Radiance HDR, PFM or
+  /// OpenEXR depending on its extension. A PPM is written to standard output if
+  /// omitted.
+  #[arg(long, short)]
+  pub output: Option<PathBuf>,
+
+  /// Also write the number of samples taken for each pixel as a heatmap, from
+  /// blue for none to red for --spp, in any of the --output formats.
+  #[arg(long)]
+  pub sample_heatmap: Option<PathBuf>,
+
+  /// Periodically save the render's progress to this file, to be continued
+  /// with --resume if the render is stopped.
+  #[arg(long)]
+  pub checkpoint: Option<PathBuf>,
+
+  /// Seconds between checkpoints. A checkpoint waits for the tiles being
+  /// rendered to finish, which may take longer on slow scenes.
+  #[arg(long, default_value_t = 60, value_parser = at_least::<1>)]
+  pub checkpoint_interval: usize,
+
+  /// Continue the render saved in this checkpoint, which keeps being updated
+  /// unless --checkpoint names another file. The scene and options must match
+  /// the ones it was started with.
+  #[arg(long)]
+  pub resume: Option<PathBuf>,
+
+  /// Compression of OpenEXR output [default: zip].
+  #[arg(long, value_enum)]
+  pub exr_compression: Option<ExrCompression>,
+
+  /// Channel type of OpenEXR output [default: half].
+  #[arg(long, value_enum)]
+  pub exr_pixel_type: Option<ExrPixelType>,
+
+  /// Image width in pixels. If only one of the width and height is given, the
+  /// other is chosen to keep the scene's aspect ratio.
+  #[arg(long, value_parser = at_least::<2>)]
+  pub width: Option<usize>,
+
+  /// Image height in pixels.
+  #[arg(long, value_parser = at_least::<2>)]
+  pub height: Option<usize>,
+
+  /// Samples taken per pixel, or the most taken with adaptive sampling.
+  #[arg(long, value_parser = at_least::<1>)]
+  pub spp: Option<usize>,
+
+  /// Sample each pixel adaptively, until the relative standard error of its
+  /// luminance falls to this (e.g. 0.01) or --spp samples were taken.
+  #[arg(long, value_parser = positive)]
+  pub adaptive_threshold: Option<f64>,
+
+  /// Samples taken before adaptive sampling may stop.
+  #[arg(long, value_parser = at_least::<2>)]
+  pub min_spp: Option<usize>,
+
+  /// Maximum number of times a ray may bounce.
+  #[arg(long, value_parser = at_least::<1>)]
+  pub max_bounces: Option<usize>,
+
+  /// Number of bounces after which paths may be ended early at random.
+  #[arg(long)]
+  pub roulette_depth: Option<usize>,
+
+  /// Number of render threads. Defaults to the number of logical CPUs.
+  #[arg(long, value_parser = at_least::<1>)]
+  pub threads: Option<usize>,
+
+  /// Seed for the random numbers. The same seed renders the same image with any
+  /// number of threads. Picked at random if omitted.
+  #[arg(long)]
+  pub seed: Option<u64>,
+
+  /// How the random numbers of a pixel's samples are spread, overriding the
+  /// scene's.
+  #[arg(long, value_enum)]
+  pub sampler: Option<SamplerKind>,
+
+  /// Algorithm used to render the image, overriding the scene's.
+  #[arg(long, value_enum)]
+  pub integrator: Option<IntegratorKind>,
+
+  /// Colour the background by ray direction, to debug refraction.
+  #[arg(long)]
+  pub debug: bool,
+}
+
+impl Args {
+  /// The OpenEXR options, if any were given.
+  pub fn exr_options(&self) -> Option<ExrOptions> {
+    if self.exr_compression.is_none() && self.exr_pixel_type.is_none() {
+      return None;
+    }
+
+    Some(ExrOptions {
+      compression: self.exr_compression.unwrap_or_default(),
+      pixel_type: self.exr_pixel_type.unwrap_or_default(),
+    })
+  }
+}
@@ -0,0 +1,6 @@
+pub mod exr;
+pub mod format;
+pub mod hdr;
+pub mod pfm;
+pub mod png;
+pub mod ppm;
@@ -0,0 +1,206 @@
+use crate::renderer::core::vec3::Color;
+use clap::ValueEnum;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use half::f16;
+use std::io::{self, Write};
+
+const MAGIC: [u8; 4] = [0x76, 0x2f, 0x31, 0x01];
+/// Version 2, for a single-part scanline image.
+const VERSION: [u8; 4] = [2, 0, 0, 0];
+
+/// Channels are stored in alphabetical order.
+const CHANNELS: [&str; 3] = ["B", "G", "R"];
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum ExrCompression {
+  None,
+  #[default]
+  Zip,
+}
+
+impl ExrCompression {
+  fn id(&self) -> u8 {
+    match self {
+      ExrCompression::None => 0,
+      ExrCompression::Zip => 3,
+    }
+  }
+
+  fn scanlines_per_block(&self) -> usize {
+    match self {
+      ExrCompression::None => 1,
+      ExrCompression::Zip => 16,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum ExrPixelType {
+  #[default]
+  Half,
+  Float,
+}
+
+impl ExrPixelType {
+  fn id(&self) -> i32 {
+    match self {
+      ExrPixelType::Half => 1,
+      ExrPixelType::Float => 2,
+    }
+  }
+
+  fn write(&self, output: &mut Vec<u8>, value: f64) {
+    match self {
+      ExrPixelType::Half => output.extend(f16::from_f64(value).to_le_bytes()),
+      ExrPixelType::Float => output.extend((value as f32).to_le_bytes()),
+    }
+  }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct ExrOptions {
+  pub compression: ExrCompression,
+  pub pixel_type: ExrPixelType,
+}
+
+fn write_attribute(header: &mut Vec<u8>, name: &str, kind: &str, value: &[u8]) {
+  header.extend(name.as_bytes());
+  header.push(0);
+  header.extend(kind.as_bytes());
+  header.push(0);
+  header.extend((value.len() as i32).to_le_bytes());
+  header.extend(value);
+}
+
+fn header(width: usize, height: usize, options: &ExrOptions) -> Vec<u8> {
+  let mut channels = Vec::new();
+  for channel in CHANNELS {
+    channels.extend(channel.as_bytes());
+    channels.push(0);
+    channels.extend(options.pixel_type.id().to_le_bytes());
+    // Perceptually linear flag and reserved bytes, then x and y sampling.
+    channels.extend([0; 4]);
+    channels.extend(1i32.to_le_bytes());
+    channels.extend(1i32.to_le_bytes());
+  }
+  channels.push(0);
+
+  let window = [0, 0, width as i32 - 1, height as i32 - 1]
+    .iter()
+    .flat_map(|value| value.to_le_bytes())
+    .collect::<Vec<_>>();
+
+  let mut header = Vec::new();
+  write_attribute(&mut header, "channels", "chlist", &channels);
+  write_attribute(
+    &mut header,
+    "compression",
+    "compression",
+    &[options.compression.id()],
+  );
+  write_attribute(&mut header, "dataWindow", "box2i", &window);
+  write_attribute(&mut header, "displayWindow", "box2i", &window);
+  // Increasing y.
+  write_attribute(&mut header, "lineOrder", "lineOrder", &[0]);
+  write_attribute(
+    &mut header,
+    "pixelAspectRatio",
+    "float",
+    &1f32.to_le_bytes(),
+  );
+  write_attribute(&mut header, "screenWindowCenter", "v2f", &[0; 8]);
+  write_attribute(
+    &mut header,
+    "screenWindowWidth",
+    "float",
+    &1f32.to_le_bytes(),
+  );
+  header.push(0);
+
+  header
+}
+
+/// Compresses a block as OpenEXR's ZIP compression does: the bytes are split
+/// into two interleaved halves and delta encoded before being deflated.
+fn zip(data: &[u8]) -> io::Result<Vec<u8>> {
+  let half = data.len().div_ceil(2);
+  let mut reordered = vec![0; data.len()];
+  for (index, byte) in data.iter().enumerate() {
+    reordered[(index / 2) + ((index % 2) * half)] = *byte;
+  }
+
+  let mut previous = reordered.first().copied().unwrap_or_default();
+  for byte in reordered.iter_mut().skip(1) {
+    let current = *byte;
+    *byte = current.wrapping_sub(previous).wrapping_add(128);
+    previous = current;
+  }
+
+  let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+  encoder.write_all(&reordered)?;
+  encoder.finish()
+}
+
+/// Writes linear `pixels`, ordered from the top left, as a single-part
+/// scanline OpenEXR image.
+pub fn write(
+  output: &mut impl Write,
+  width: usize,
+  height: usize,
+  pixels: &[Color],
+  options: &ExrOptions,
+) -> io::Result<()> {
+  debug_assert_eq!(pixels.len(), width * height);
+
+  let header = header(width, height, options);
+  let scanlines_per_block = options.compression.scanlines_per_block();
+
+  let mut blocks = Vec::new();
+  for (index, rows) in pixels.chunks(width * scanlines_per_block).enumerate() {
+    let mut data = Vec::new();
+    for row in rows.chunks(width) {
+      for channel in CHANNELS {
+        for pixel in row {
+          let value = match channel {
+            "R" => pixel.x(),
+            "G" => pixel.y(),
+            _ => pixel.z(),
+          };
+
+          options.pixel_type.write(&mut data, value);
+        }
+      }
+    }
+
+    if let ExrCompression::Zip = options.compression {
+      let compressed = zip(&data)?;
+
+      // Blocks which do not shrink are stored uncompressed.
+      if compressed.len() < data.len() {
+        data = compressed;
+      }
+    }
+
+    blocks.push(((index * scanlines_per_block) as i32, data));
+  }
+
+  // The offset table follows the header, and each block is prefixed by its
+  // first scanline and its size.
+  let mut offset = (MAGIC.len() + VERSION.len() + header.len() + (8 * blocks.len())) as u64;
+
+  output.write_all(&MAGIC)?;
+  output.write_all(&VERSION)?;
+  output.write_all(&header)?;
+  for (_, data) in &blocks {
+    output.write_all(&offset.to_le_bytes())?;
+    offset += 8 + data.len() as u64;
+  }
+  for (scanline, data) in &blocks {
+    output.write_all(&scanline.to_le_bytes())?;
+    output.write_all(&(data.len() as i32).to_le_bytes())?;
+    output.write_all(data)?;
+  }
+
+  output.flush()
+}
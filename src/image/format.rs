@@ -0,0 +1,97 @@
+use crate::image::exr::{self, ExrOptions};
+use crate::image::{hdr, pfm, png, ppm};
+use crate::renderer::core::color;
+use crate::renderer::core::vec3::Color;
+use crate::types::result::Result;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// A linear high dynamic range image, ordered from the top left.
+pub struct LinearImage {
+  pub width: usize,
+  pub height: usize,
+  pub pixels: Vec<Color>,
+}
+
+#[derive(Clone, Copy)]
+pub enum ImageFormat {
+  Png,
+  Ppm,
+  Hdr,
+  Pfm,
+  Exr(ExrOptions),
+}
+
+impl ImageFormat {
+  /// Picks the format matching the extension of `path`.
+  pub fn from_path(path: &Path) -> Result<Self> {
+    let extension = path
+      .extension()
+      .and_then(|extension| extension.to_str())
+      .map(|extension| extension.to_ascii_lowercase());
+
+    match extension.as_deref() {
+      Some("png") => Ok(ImageFormat::Png),
+      Some("ppm") => Ok(ImageFormat::Ppm),
+      Some("hdr") => Ok(ImageFormat::Hdr),
+      Some("pfm") => Ok(ImageFormat::Pfm),
+      Some("exr") => Ok(ImageFormat::Exr(ExrOptions::default())),
+      _ => Err(
+        format!(
+          "Cannot tell the image format of {}; use a .png, .ppm, .hdr, .pfm or .exr extension.",
+          path.display()
+        )
+        .into(),
+      ),
+    }
+  }
+
+  /// Writes linear `pixels`, ordered from the top left. Low dynamic range
+  /// formats are quantized to 8-bit sRGB.
+  pub fn write(
+    &self,
+    output: &mut impl Write,
+    width: usize,
+    height: usize,
+    pixels: &[Color],
+  ) -> io::Result<()> {
+    let quantized = || {
+      pixels
+        .iter()
+        .copied()
+        .map(color::to_rgb8)
+        .collect::<Vec<_>>()
+    };
+
+    match self {
+      ImageFormat::Png => png::write(output, width, height, &quantized()),
+      ImageFormat::Ppm => ppm::write(output, width, height, &quantized()),
+      ImageFormat::Hdr => hdr::write(output, width, height, pixels),
+      ImageFormat::Pfm => pfm::write(output, width, height, pixels),
+      ImageFormat::Exr(options) => exr::write(output, width, height, pixels, options),
+    }
+  }
+
+  /// Reads the high dynamic range image at `path`.
+  pub fn read(path: &Path) -> Result<LinearImage> {
+    let data =
+      fs::read(path).map_err(|err| format!("Could not read image {}: {err}", path.display()))?;
+
+    let image = match Self::from_path(path)? {
+      ImageFormat::Hdr => hdr::read(&data),
+      ImageFormat::Pfm => pfm::read(&data),
+      _ => {
+        return Err(
+          format!(
+            "Cannot read {}; only .hdr and .pfm images are supported.",
+            path.display()
+          )
+          .into(),
+        )
+      }
+    };
+
+    image.map_err(|err| format!("Could not read image {}: {err}", path.display()).into())
+  }
+}
@@ -0,0 +1,190 @@
+use crate::image::format::LinearImage;
+use crate::renderer::core::vec3::Color;
+use crate::types::result::Result;
+use std::io::{self, Write};
+
+/// Encodes a color as RGBE: three 8-bit mantissas sharing an 8-bit exponent.
+fn to_rgbe(color: Color) -> [u8; 4] {
+  let (red, green, blue) = color.map(|component| component.max(0.0)).tuple();
+  let max = red.max(green).max(blue);
+
+  if max < 1e-32 {
+    return [0; 4];
+  }
+
+  // Split `max` into a mantissa in [0.5, 1) and a power of two.
+  let exponent = max.log2().floor() as i32 + 1;
+  let scale = 256.0 / 2f64.powi(exponent);
+
+  [
+    (red * scale) as u8,
+    (green * scale) as u8,
+    (blue * scale) as u8,
+    (exponent + 128) as u8,
+  ]
+}
+
+/// Writes linear `pixels`, ordered from the top left, as an uncompressed
+/// Radiance RGBE image.
+pub fn write(
+  output: &mut impl Write,
+  width: usize,
+  height: usize,
+  pixels: &[Color],
+) -> io::Result<()> {
+  debug_assert_eq!(pixels.len(), width * height);
+
+  write!(
+    output,
+    "#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y {height} +X {width}\n"
+  )?;
+  for pixel in pixels {
+    output.write_all(&to_rgbe(*pixel))?;
+  }
+
+  output.flush()
+}
+
+fn from_rgbe([red, green, blue, exponent]: [u8; 4]) -> Color {
+  if exponent == 0 {
+    return Color::default();
+  }
+
+  // Take the middle of each mantissa's range, as Radiance does.
+  let scale = 2f64.powi(exponent as i32 - (128 + 8));
+  Color::new(
+    (red as f64 + 0.5) * scale,
+    (green as f64 + 0.5) * scale,
+    (blue as f64 + 0.5) * scale,
+  )
+}
+
+/// Splits the next newline-terminated line off `data`.
+fn next_line<'a>(data: &mut &'a [u8]) -> Result<&'a str> {
+  let end = data
+    .iter()
+    .position(|&byte| byte == b'\n')
+    .ok_or("Unexpected end of header.")?;
+  let line = std::str::from_utf8(&data[..end]).map_err(|_| "Header is not text.")?;
+  *data = &data[end + 1..];
+
+  Ok(line)
+}
+
+/// Decodes one adaptively run-length encoded scanline, whose four components
+/// are stored one after another.
+fn read_rle_scanline(data: &mut &[u8], scanline: &mut [[u8; 4]]) -> Result<()> {
+  let width = scanline.len();
+
+  for component in 0..4 {
+    let mut x = 0;
+
+    while x < width {
+      let (&count, rest) = data.split_first().ok_or("Scanline data ends early.")?;
+      *data = rest;
+
+      if count > 128 {
+        let count = count as usize - 128;
+        let (&value, rest) = data.split_first().ok_or("Scanline data ends early.")?;
+        *data = rest;
+
+        if x + count > width {
+          return Err("Run overflows scanline.".into());
+        }
+        for pixel in &mut scanline[x..x + count] {
+          pixel[component] = value;
+        }
+        x += count;
+      } else {
+        let count = count as usize;
+
+        if (count == 0) || (x + count > width) || (data.len() < count) {
+          return Err("Invalid literal run in scanline.".into());
+        }
+        for (pixel, &value) in scanline[x..x + count].iter_mut().zip(&data[..count]) {
+          pixel[component] = value;
+        }
+        *data = &data[count..];
+        x += count;
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Reads a Radiance RGBE image, either flat or run-length encoded.
+pub fn read(mut data: &[u8]) -> Result<LinearImage> {
+  let signature = next_line(&mut data)?;
+  if !signature.starts_with("#?") {
+    return Err("Not a Radiance image.".into());
+  }
+
+  loop {
+    let line = next_line(&mut data)?;
+
+    if line.is_empty() {
+      break;
+    }
+    if let Some(format) = line.strip_prefix("FORMAT=") {
+      if format != "32-bit_rle_rgbe" {
+        return Err(format!("Unsupported pixel format `{format}`.").into());
+      }
+    }
+  }
+
+  let resolution = next_line(&mut data)?;
+  let (height, width) = match resolution.split_whitespace().collect::<Vec<_>>()[..] {
+    ["-Y", height, "+X", width] => (height.parse::<usize>(), width.parse::<usize>()),
+    _ => return Err(format!("Unsupported image orientation `{resolution}`.").into()),
+  };
+  let (height, width) = height
+    .ok()
+    .zip(width.ok())
+    .filter(|&(height, width)| (height > 0) && (width > 0))
+    .ok_or_else(|| format!("Invalid resolution `{resolution}`."))?;
+
+  // Even run-length encoded, every scanline takes a few bytes for each 127
+  // pixels, so a resolution the data could not hold is rejected before the
+  // pixels are allocated.
+  let min_scanline_length = width.saturating_mul(4).min(4 + 8 * width.div_ceil(127));
+  let pixel_count = width
+    .checked_mul(height)
+    .filter(|_| {
+      height
+        .checked_mul(min_scanline_length)
+        .is_some_and(|length| length <= data.len())
+    })
+    .ok_or("Pixel data ends early.")?;
+
+  let mut pixels = Vec::with_capacity(pixel_count);
+  let mut scanline = vec![[0; 4]; width];
+
+  for _ in 0..height {
+    let run_length_encoded = (8..0x8000).contains(&width)
+      && (data.len() >= 4)
+      && (data[..2] == [2, 2])
+      && (((data[2] as usize) << 8) | data[3] as usize == width);
+
+    if run_length_encoded {
+      data = &data[4..];
+      read_rle_scanline(&mut data, &mut scanline)?;
+    } else {
+      if data.len() < width * 4 {
+        return Err("Scanline data ends early.".into());
+      }
+      for (pixel, bytes) in scanline.iter_mut().zip(data.chunks_exact(4)) {
+        pixel.copy_from_slice(bytes);
+      }
+      data = &data[width * 4..];
+    }
+
+    pixels.extend(scanline.iter().copied().map(from_rgbe));
+  }
+
+  Ok(LinearImage {
+    width,
+    height,
+    pixels,
+  })
+}
@@ -0,0 +1,110 @@
+use crate::image::format::LinearImage;
+use crate::renderer::core::vec3::Color;
+use crate::types::result::Result;
+use std::io::{self, Write};
+
+/// Writes linear `pixels`, ordered from the top left, as a little-endian
+/// Portable Float Map.
+pub fn write(
+  output: &mut impl Write,
+  width: usize,
+  height: usize,
+  pixels: &[Color],
+) -> io::Result<()> {
+  debug_assert_eq!(pixels.len(), width * height);
+
+  // A negative scale marks the data as little-endian.
+  write!(output, "PF\n{width} {height}\n-1.0\n")?;
+
+  // Scanlines are stored from the bottom up.
+  for row in pixels.chunks(width).rev() {
+    for pixel in row {
+      let (red, green, blue) = pixel.tuple();
+
+      for component in [red, green, blue] {
+        output.write_all(&(component as f32).to_le_bytes())?;
+      }
+    }
+  }
+
+  output.flush()
+}
+
+/// Splits the next whitespace-delimited header token off `data`.
+fn next_token<'a>(data: &mut &'a [u8]) -> Result<&'a str> {
+  let start = data
+    .iter()
+    .position(|byte| !byte.is_ascii_whitespace())
+    .ok_or("Unexpected end of header.")?;
+  let length = data[start..]
+    .iter()
+    .position(|byte| byte.is_ascii_whitespace())
+    .ok_or("Unexpected end of header.")?;
+  let token =
+    std::str::from_utf8(&data[start..start + length]).map_err(|_| "Header is not text.")?;
+  // Exactly one whitespace character separates the header from the data.
+  *data = &data[start + length + 1..];
+
+  Ok(token)
+}
+
+/// Reads a colour or greyscale Portable Float Map of either endianness.
+pub fn read(mut data: &[u8]) -> Result<LinearImage> {
+  let channels = match next_token(&mut data)? {
+    "PF" => 3,
+    "Pf" => 1,
+    _ => return Err("Not a Portable Float Map.".into()),
+  };
+
+  let mut dimension = || -> Result<usize> {
+    next_token(&mut data)?
+      .parse::<usize>()
+      .ok()
+      .filter(|&dimension| dimension > 0)
+      .ok_or_else(|| "Invalid image dimensions.".into())
+  };
+  let width = dimension()?;
+  let height = dimension()?;
+
+  let scale = next_token(&mut data)?
+    .parse::<f64>()
+    .ok()
+    .filter(|scale| scale.is_finite() && (*scale != 0.0))
+    .ok_or("Invalid scale.")?;
+  let decode = if scale < 0.0 {
+    f32::from_le_bytes
+  } else {
+    f32::from_be_bytes
+  };
+
+  let length = width
+    .checked_mul(height)
+    .and_then(|samples| samples.checked_mul(channels * 4))
+    .ok_or("Invalid image dimensions.")?;
+  if data.len() < length {
+    return Err("Pixel data ends early.".into());
+  }
+
+  let samples = data[..length]
+    .chunks_exact(4)
+    .map(|bytes| decode(bytes.try_into().unwrap()) as f64)
+    .collect::<Vec<_>>();
+
+  // Scanlines are stored from the bottom up.
+  let pixels = samples
+    .chunks_exact(width * channels)
+    .rev()
+    .flat_map(|row| row.chunks_exact(channels))
+    .map(|pixel| match *pixel {
+      [grey] => Color::from(grey),
+      [red, green, blue] => Color::new(red, green, blue),
+      _ => unreachable!(),
+    })
+    .collect();
+
+  Ok(LinearImage {
+    width,
+    height,
+    pixels,
+  })
+}
@@ -0,0 +1,54 @@
+use flate2::write::ZlibEncoder;
+use flate2::{Compression, Crc};
+use std::io::{self, Write};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+const BIT_DEPTH: u8 = 8;
+const COLOR_TYPE_RGB: u8 = 2;
+const FILTER_NONE: u8 = 0;
+const RENDERING_INTENT_PERCEPTUAL: u8 = 0;
+
+fn write_chunk(output: &mut impl Write, chunk_type: &[u8; 4], data: &[u8]) -> io::Result<()> {
+  let mut crc = Crc::new();
+  crc.update(chunk_type);
+  crc.update(data);
+
+  output.write_all(&(data.len() as u32).to_be_bytes())?;
+  output.write_all(chunk_type)?;
+  output.write_all(data)?;
+  output.write_all(&crc.sum().to_be_bytes())
+}
+
+/// Writes 8-bit sRGB `pixels`, ordered from the top left, as a PNG.
+pub fn write(
+  output: &mut impl Write,
+  width: usize,
+  height: usize,
+  pixels: &[[u8; 3]],
+) -> io::Result<()> {
+  debug_assert_eq!(pixels.len(), width * height);
+
+  let header = [
+    (width as u32).to_be_bytes().as_slice(),
+    &(height as u32).to_be_bytes(),
+    // Compression, filter and interlace methods are all the default.
+    &[BIT_DEPTH, COLOR_TYPE_RGB, 0, 0, 0],
+  ]
+  .concat();
+
+  let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+  for row in pixels.chunks(width) {
+    encoder.write_all(&[FILTER_NONE])?;
+    encoder.write_all(row.as_flattened())?;
+  }
+  let data = encoder.finish()?;
+
+  output.write_all(&SIGNATURE)?;
+  write_chunk(output, b"IHDR", &header)?;
+  write_chunk(output, b"sRGB", &[RENDERING_INTENT_PERCEPTUAL])?;
+  write_chunk(output, b"IDAT", &data)?;
+  write_chunk(output, b"IEND", &[])?;
+
+  output.flush()
+}
@@ -0,0 +1,16 @@
+use std::io::{self, Write};
+
+/// Writes 8-bit RGB `pixels`, ordered from the top left, as a binary (P6) PPM.
+pub fn write(
+  output: &mut impl Write,
+  width: usize,
+  height: usize,
+  pixels: &[[u8; 3]],
+) -> io::Result<()> {
+  debug_assert_eq!(pixels.len(), width * height);
+
+  write!(output, "P6\n{width} {height}\n255\n")?;
+  output.write_all(pixels.as_flattened())?;
+
+  output.flush()
+}
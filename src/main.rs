@@ -2,163 +2,206 @@
 
 #[allow(unused_imports)]
 mod benchmark;
+mod cli;
+mod image;
 mod renderer;
 mod types;
 
+use crate::cli::Args;
+use crate::image::format::ImageFormat;
+use crate::renderer::checkpoint::{self, Checkpoint};
 use crate::renderer::core::color;
 use crate::renderer::core::vec2::Vec2;
-use crate::renderer::core::vec3::{Color, Point3, Vec3};
-use crate::renderer::materials::dielectric::Dielectric;
-use crate::renderer::materials::lambertian::Lambertian;
-use crate::renderer::materials::material::Material;
-use crate::renderer::materials::metal::Metal;
-use crate::renderer::render::{self, RenderParams, Scene};
-use crate::renderer::scene::camera::Camera;
-use crate::renderer::scene::sphere::Sphere;
+use crate::renderer::core::vec3::Color;
+use crate::renderer::film::Film;
+use crate::renderer::loaders::scene_file;
+use crate::renderer::render::RenderParams;
+use crate::renderer::scheduler;
 use crate::types::result::Result;
-use std::sync::mpsc::{Receiver, Sender};
-use std::sync::{mpsc, Arc};
-use std::{env, thread};
-
-const MAX_BOUNCES: usize = 50;
-const SAMPLES_PER_PIXEL: usize = 100;
-const ASPECT_RATIO: f64 = 16.0 / 9.0;
-
-const IMAGE_WIDTH: usize = 400;
-const IMAGE_HEIGHT: usize = (IMAGE_WIDTH as f64 / ASPECT_RATIO) as usize;
-
-const IMAGE_PIXELS: usize = IMAGE_WIDTH * IMAGE_HEIGHT;
+use clap::Parser;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+use std::process;
+use std::time::{Duration, Instant};
+
+/// The number of samples per pixel taken in each pass over the image.
+const SAMPLES_PER_PASS: usize = 16;
+
+/// Applies the command line overrides to the scene's render settings.
+fn configure(args: &Args, params: &mut RenderParams) -> Result<()> {
+  let (scene_width, scene_height) = (params.image_size.x(), params.image_size.y());
+  let scale =
+    |length: usize, from: usize, to: usize| ((length * to) as f64 / from as f64).round() as usize;
+
+  let (width, height) = match (args.width, args.height) {
+    (Some(width), Some(height)) => (width, height),
+    (Some(width), None) => (width, scale(width, scene_width, scene_height)),
+    (None, Some(height)) => (scale(height, scene_height, scene_width), height),
+    (None, None) => (scene_width, scene_height),
+  };
 
-const LAST_PIXEL_X: usize = IMAGE_WIDTH - 1;
-const LAST_PIXEL_Y: usize = IMAGE_HEIGHT - 1;
+  if (width < 2) || (height < 2) {
+    // Only possible when one side was derived from the scene's aspect ratio.
+    return Err(
+      format!(
+        "Keeping the scene's {scene_width}x{scene_height} aspect ratio gives a {width}x{height} \
+         image, which is too small. Pass both --width and --height."
+      )
+      .into(),
+    );
+  }
 
-struct RenderMessage(usize, Color);
+  params.image_size = Vec2::new(width, height);
+  params.samples_per_pixel = args.spp.unwrap_or(params.samples_per_pixel);
+  params.adaptive_threshold = args.adaptive_threshold.or(params.adaptive_threshold);
+  params.min_samples_per_pixel = args.min_spp.unwrap_or(params.min_samples_per_pixel);
+  params.max_bounces = args.max_bounces.unwrap_or(params.max_bounces);
+  params.roulette_depth = args.roulette_depth.unwrap_or(params.roulette_depth);
+  params.seed = args.seed.unwrap_or_else(rand::random);
+  params.sampler = args.sampler.unwrap_or(params.sampler);
+  params.integrator = args.integrator.unwrap_or(params.integrator);
+  params.debug |= args.debug;
+
+  if params.adaptive_threshold.is_none() {
+    if args.min_spp.is_some() {
+      return Err("--min-spp only applies with --adaptive-threshold.".into());
+    }
+  } else if params.min_samples_per_pixel > params.samples_per_pixel {
+    return Err(
+      format!(
+        "Adaptive sampling takes at least {} samples per pixel, more than the {} allowed. Lower \
+         --min-spp or raise --spp.",
+        params.min_samples_per_pixel, params.samples_per_pixel
+      )
+      .into(),
+    );
+  }
 
-struct RenderThreadContext {
-  thread_id: usize,
-  threads: usize,
-  scanlines_per_thread: usize,
-  scanline_max: usize,
-  sender: Sender<RenderMessage>,
+  Ok(())
 }
 
-fn render_thread(
-  context: RenderThreadContext,
-  params: RenderParams,
-  scene: Arc<Scene>,
+fn write_image(
+  path: &Path,
+  format: ImageFormat,
+  width: usize,
+  height: usize,
+  pixels: &[Color],
 ) -> Result<()> {
-  for iteration in 0..context.scanlines_per_thread {
-    let row = context.thread_id + (iteration * context.threads);
+  let file =
+    File::create(path).map_err(|err| format!("Could not create {}: {err}", path.display()))?;
+  format.write(&mut BufWriter::new(file), width, height, pixels)?;
 
-    if row >= context.scanline_max {
-      break;
-    }
-
-    let row_index = row * IMAGE_WIDTH;
+  Ok(())
+}
 
-    for col in 0..IMAGE_WIDTH {
-      context.sender.send(RenderMessage(
-        row_index + LAST_PIXEL_X - col,
-        render::render_pixel(&params, &scene, Vec2::new(col, row)),
-      ))?;
+fn render_scene(args: &Args) -> Result<()> {
+  // Check the output format before rendering, rather than failing afterwards.
+  let format = match (&args.output, args.exr_options()) {
+    (Some(path), exr_options) => match (ImageFormat::from_path(path)?, exr_options) {
+      (ImageFormat::Exr(_), Some(options)) => ImageFormat::Exr(options),
+      (format, None) => format,
+      (_, Some(_)) => {
+        return Err("--exr-compression and --exr-pixel-type only apply to .exr output.".into())
+      }
+    },
+    (None, None) => ImageFormat::Ppm,
+    (None, Some(_)) => {
+      return Err("--exr-compression and --exr-pixel-type need an .exr --output.".into())
     }
-  }
+  };
+  let heatmap_format = args
+    .sample_heatmap
+    .as_deref()
+    .map(ImageFormat::from_path)
+    .transpose()?;
+  let resumed = args.resume.as_deref().map(Checkpoint::read).transpose()?;
+  let (params, scene) = scene_file::load(&args.scene, |params| {
+    configure(args, params)?;
+
+    if let (Some(checkpoint), None) = (&resumed, args.seed) {
+      params.seed = checkpoint.seed;
+    }
+    Ok(())
+  })?;
+  eprintln!("Rendering with --seed {}", params.seed);
+
+  let threads = args.threads.unwrap_or_else(num_cpus::get);
+  eprintln!(
+    "Running {threads} threads on {} cores...",
+    num_cpus::get_physical()
+  );
 
-  Ok(())
-}
+  let checkpoint_path = args.checkpoint.as_deref().or(args.resume.as_deref());
+  let scene_hash = match checkpoint_path {
+    Some(_) => checkpoint::scene_hash(&scene)?,
+    None => 0,
+  };
 
-fn spawn_render_threads(params: RenderParams, scene: Arc<Scene>) -> Receiver<RenderMessage> {
-  let (sender, receiver) = mpsc::channel();
+  let mut film = match resumed {
+    Some(checkpoint) => {
+      checkpoint.check(&params, scene_hash)?;
+      checkpoint.film
+    }
+    None => Film::new(params.image_size),
+  };
 
-  let threads = {
-    let threads = num_cpus::get();
-    let cores = num_cpus::get_physical();
+  let checkpoint_interval = Duration::from_secs(args.checkpoint_interval as u64);
+  let mut last_checkpoint = Instant::now();
 
-    eprintln!("Running {threads} threads on {cores} cores...");
+  while film.samples() < params.samples_per_pixel {
+    let samples = (film.samples() + SAMPLES_PER_PASS).min(params.samples_per_pixel);
+    // A pass cut short by the deadline is picked up again by the next one.
+    let deadline = checkpoint_path.map(|_| last_checkpoint + checkpoint_interval);
+    scheduler::render(&params, &scene, &mut film, samples, threads, deadline);
 
-    threads
-  };
-  let scanlines_per_thread = (IMAGE_HEIGHT as f64 / threads as f64).ceil() as usize;
-
-  for thread_id in 0..threads {
-    let context = RenderThreadContext {
-      thread_id,
-      threads,
-      scanlines_per_thread,
-      scanline_max: IMAGE_HEIGHT,
-      sender: sender.clone(),
-    };
-    let params = RenderParams {
-      last_pixel: Vec2::new(LAST_PIXEL_X, LAST_PIXEL_Y),
-      samples_per_pixel: SAMPLES_PER_PIXEL,
-      max_bounces: MAX_BOUNCES,
-      ..params
-    };
-    let scene = Arc::clone(&scene);
-
-    thread::spawn(move || {
-      render_thread(context, params, scene).expect("Render thread did not execute successfully.");
-    });
+    if let Some(path) = checkpoint_path {
+      if last_checkpoint.elapsed() >= checkpoint_interval {
+        Checkpoint::write(path, &params, scene_hash, &film)?;
+        last_checkpoint = Instant::now();
+      }
+    }
   }
 
-  receiver
-}
-
-fn main() -> Result<()> {
-  let debug = env::args().any(|val| val == "-debug");
-
-  let ground: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.8, 0.8, 0.0)));
-  let left: Arc<dyn Material> = Arc::new(Dielectric::new(1.5));
-  let center: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.1, 0.2, 0.5)));
-  let right: Arc<dyn Material> = Arc::new(Metal::new(Color::new(0.8, 0.6, 0.2), 0.0));
-  let world = Box::new(if debug {
-    vec![Sphere::new(
-      Point3::new(0.0, 0.0, -1.0),
-      0.5,
-      Arc::clone(&left),
-    )]
-  } else {
-    vec![
-      Sphere::new(Point3::new(0.0, -100.5, -1.0), 100.0, Arc::clone(&ground)),
-      Sphere::new(Point3::new(0.0, 0.0, -1.0), 0.5, Arc::clone(&center)),
-      Sphere::new(Point3::new(-1.0, 0.0, -1.0), 0.5, Arc::clone(&left)),
-      Sphere::new(Point3::new(-1.0, 0.0, -1.0), -0.4, Arc::clone(&left)),
-      Sphere::new(Point3::new(1.0, 0.0, -1.0), 0.5, Arc::clone(&right)),
-    ]
-  });
-
-  let camera = Camera::new(ASPECT_RATIO);
-
-  let mut image = vec![Vec3::default(); IMAGE_PIXELS];
-
-  let receiver = spawn_render_threads(
-    RenderParams {
-      debug,
-      ..RenderParams::default()
-    },
-    Arc::new(Scene { camera, world }),
-  );
+  // Also save the finished film, in case writing the image fails.
+  if let Some(path) = checkpoint_path {
+    Checkpoint::write(path, &params, scene_hash, &film)?;
+  }
 
-  let mut scanlines_remaining = IMAGE_HEIGHT;
-  for _ in 0..IMAGE_PIXELS {
-    let RenderMessage(index, color) = receiver.recv()?;
-    image[index] = color;
+  let image_width = params.image_size.x();
+  let image_height = params.image_size.y();
+  let pixels = film.colors();
+  let sample_counts = film.sample_counts();
 
-    if (index % IMAGE_WIDTH) == LAST_PIXEL_X {
-      scanlines_remaining -= 1;
-      eprint!("\rScanlines remaining: {scanlines_remaining}  ");
-    }
+  match &args.output {
+    Some(path) => write_image(path, format, image_width, image_height, &pixels)?,
+    None => format.write(&mut io::stdout().lock(), image_width, image_height, &pixels)?,
   }
 
-  println!("P3");
-  println!("{IMAGE_WIDTH} {IMAGE_HEIGHT}");
-  println!("255");
-  for pixel_color in image.into_iter().rev() {
-    color::print_color(pixel_color);
+  if let (Some(path), Some(format)) = (&args.sample_heatmap, heatmap_format) {
+    let heatmap = sample_counts
+      .iter()
+      .map(|&count| color::heatmap(count as f64 / params.samples_per_pixel as f64))
+      .collect::<Vec<_>>();
+    write_image(path, format, image_width, image_height, &heatmap)?;
   }
 
   eprintln!();
+  if params.adaptive_threshold.is_some() {
+    let samples = sample_counts.iter().sum::<usize>();
+    eprintln!(
+      "Took {:.1} samples per pixel on average.",
+      samples as f64 / sample_counts.len() as f64
+    );
+  }
   eprintln!("Done.");
 
   Ok(())
 }
+
+fn main() {
+  if let Err(err) = render_scene(&Args::parse()) {
+    eprintln!("Error: {err}");
+    process::exit(1);
+  }
+}
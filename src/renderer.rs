@@ -1,4 +1,10 @@
+pub mod checkpoint;
 pub mod core;
+pub mod film;
+pub mod integrators;
+pub mod loaders;
 pub mod materials;
 pub mod render;
+pub mod samplers;
 pub mod scene;
+pub mod scheduler;
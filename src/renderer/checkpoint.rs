@@ -0,0 +1,174 @@
+use crate::renderer::core::statistics::RunningStats;
+use crate::renderer::core::vec2::Vec2;
+use crate::renderer::film::Film;
+use crate::renderer::render::{MisHeuristic, RenderParams, Scene};
+use crate::types::result::Result;
+use clap::ValueEnum;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+const MAGIC: &str = "ray-tracing checkpoint 2";
+
+/// A partly rendered film, with the seed, settings and scene it was rendered
+/// with so that rendering can carry on where it stopped.
+pub struct Checkpoint {
+  pub seed: u64,
+  settings: String,
+  scene_hash: u64,
+  pub film: Film,
+}
+
+fn value_name(value: impl ValueEnum) -> String {
+  value
+    .to_possible_value()
+    .map_or_else(String::new, |value| value.get_name().to_owned())
+}
+
+/// Every render setting which affects the image, as one line.
+fn settings(params: &RenderParams) -> String {
+  let mis_heuristic = match params.mis_heuristic {
+    MisHeuristic::Balance => "balance",
+    MisHeuristic::Power => "power",
+  };
+  let adaptive_threshold = params
+    .adaptive_threshold
+    .map_or_else(|| "none".to_owned(), |threshold| threshold.to_string());
+
+  format!(
+    "size={}x{} spp={} adaptive_threshold={adaptive_threshold} min_spp={} max_bounces={} \
+     roulette_depth={} seed={} sampler={} integrator={} mis_heuristic={mis_heuristic} \
+     occlusion_distance={} debug={}",
+    params.image_size.x(),
+    params.image_size.y(),
+    params.samples_per_pixel,
+    params.min_samples_per_pixel,
+    params.max_bounces,
+    params.roulette_depth,
+    params.seed,
+    value_name(params.sampler),
+    value_name(params.integrator),
+    params.occlusion_distance,
+    params.debug,
+  )
+}
+
+/// Hashes the contents of the files `scene` was loaded from with 64-bit
+/// FNV-1a, which unlike the standard library's hashers stays the same between
+/// builds.
+pub fn scene_hash(scene: &Scene) -> Result<u64> {
+  let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+
+  for path in &scene.inputs {
+    let data = fs::read(path).map_err(|err| format!("Could not read {}: {err}", path.display()))?;
+
+    for byte in (data.len() as u64).to_le_bytes().iter().chain(&data) {
+      hash = (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+    }
+  }
+
+  Ok(hash)
+}
+
+impl Checkpoint {
+  /// Saves `film` rendered with `params` of the scene with `scene_hash` to
+  /// `path`. The previous checkpoint is only replaced once the new one is
+  /// completely written, so being stopped while saving cannot lose it.
+  pub fn write(path: &Path, params: &RenderParams, scene_hash: u64, film: &Film) -> Result<()> {
+    let partial = path.with_extension("partial");
+    let create_error = |err| format!("Could not write checkpoint {}: {err}", partial.display());
+
+    let mut output = BufWriter::new(File::create(&partial).map_err(create_error)?);
+    write!(
+      output,
+      "{MAGIC}\n{}\n{}\n{scene_hash:016x}\n{} {} {}\n",
+      params.seed,
+      settings(params),
+      film.size().x(),
+      film.size().y(),
+      film.samples()
+    )?;
+    for pixel in film.pixels() {
+      pixel.write(&mut output)?;
+    }
+    output
+      .into_inner()
+      .map_err(|err| err.into_error())?
+      .sync_all()?;
+
+    fs::rename(&partial, path)?;
+
+    Ok(())
+  }
+
+  pub fn read(path: &Path) -> Result<Self> {
+    let data = fs::read(path)
+      .map_err(|err| format!("Could not read checkpoint {}: {err}", path.display()))?;
+    let invalid = || format!("{} is not a valid checkpoint.", path.display());
+
+    let mut lines = data.splitn(6, |&byte| byte == b'\n');
+    let mut line = || -> Result<&str> {
+      let line = lines.next().ok_or_else(invalid)?;
+      Ok(std::str::from_utf8(line).map_err(|_| invalid())?)
+    };
+
+    if line()? != MAGIC {
+      return Err(invalid().into());
+    }
+    let seed = line()?.parse::<u64>().map_err(|_| invalid())?;
+    let settings = line()?.to_owned();
+    let scene_hash = u64::from_str_radix(line()?, 16).map_err(|_| invalid())?;
+    let [width, height, samples] = line()?
+      .split(' ')
+      .map(|number| number.parse::<usize>())
+      .collect::<std::result::Result<Vec<_>, _>>()
+      .ok()
+      .and_then(|numbers| numbers.try_into().ok())
+      .ok_or_else(invalid)?;
+
+    let pixel_data = lines.next().unwrap_or_default();
+    let length = width
+      .checked_mul(height)
+      .and_then(|pixels| pixels.checked_mul(RunningStats::BYTES));
+    if length != Some(pixel_data.len()) {
+      return Err(invalid().into());
+    }
+    let pixels = pixel_data
+      .chunks_exact(RunningStats::BYTES)
+      .map(RunningStats::read)
+      .collect();
+
+    Ok(Self {
+      seed,
+      settings,
+      scene_hash,
+      film: Film::from_pixels(Vec2::new(width, height), samples, pixels),
+    })
+  }
+
+  /// Checks that the checkpoint was rendered with `params` of the scene with
+  /// `scene_hash`, so that resuming it gives the image an uninterrupted render
+  /// would.
+  pub fn check(&self, params: &RenderParams, scene_hash: u64) -> Result<()> {
+    let current = settings(params);
+
+    if self.scene_hash != scene_hash {
+      Err(
+        "The scene or the files it uses changed since the checkpoint was saved. Resume it with \
+         the same scene."
+          .into(),
+      )
+    } else if self.settings == current {
+      Ok(())
+    } else {
+      Err(
+        format!(
+          "The checkpoint was rendered with different settings. Resume it with the same scene \
+           and options.\n  checkpoint: {}\n  now:        {current}",
+          self.settings
+        )
+        .into(),
+      )
+    }
+  }
+}
@@ -1,8 +1,14 @@
+pub mod aabb;
 pub mod color;
 pub mod diffuse;
+pub mod distribution;
 pub mod math;
+pub mod onb;
 pub mod quadratic;
+pub mod quartic;
 pub mod random;
 pub mod ray;
+pub mod statistics;
+pub mod transform;
 pub mod vec2;
 pub mod vec3;
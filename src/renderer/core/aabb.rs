@@ -0,0 +1,88 @@
+use crate::renderer::core::math::Range;
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec3::{Point3, Vec3};
+
+/// An axis-aligned bounding box spanning [min, max] on every axis.
+#[derive(Debug, Clone, Copy)]
+pub struct Aabb {
+  min: Point3,
+  max: Point3,
+}
+
+impl Aabb {
+  pub fn new(min: Point3, max: Point3) -> Self {
+    Self { min, max }
+  }
+
+  /// Creates the smallest box containing every point in `points`.
+  pub fn from_points(points: &[Point3]) -> Self {
+    debug_assert!(!points.is_empty(), "Cannot bound an empty set of points.");
+
+    let first = Aabb::new(points[0], points[0]);
+    points[1..].iter().fold(first, |aabb, point| {
+      aabb.surrounding(&Aabb::new(*point, *point))
+    })
+  }
+
+  pub fn min(&self) -> Point3 {
+    self.min
+  }
+
+  pub fn max(&self) -> Point3 {
+    self.max
+  }
+
+  pub fn extent(&self) -> Vec3 {
+    self.max - self.min
+  }
+
+  pub fn centroid(&self) -> Point3 {
+    0.5 * (self.min + self.max)
+  }
+
+  pub fn surrounding(&self, other: &Aabb) -> Aabb {
+    Aabb::new(self.min.min(&other.min), self.max.max(&other.max))
+  }
+
+  pub fn surface_area(&self) -> f64 {
+    let (x, y, z) = self.extent().tuple();
+    2.0 * ((x * y) + (y * z) + (z * x))
+  }
+
+  /// The axis along which the box is largest.
+  pub fn longest_axis(&self) -> usize {
+    let extent = self.extent();
+
+    (0..3)
+      .max_by(|&a, &b| extent.axis(a).total_cmp(&extent.axis(b)))
+      .unwrap()
+  }
+
+  /// Tests whether `ray` passes through the box within `hittable_range` using
+  /// the slab method.
+  pub fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> bool {
+    let mut start = hittable_range.start();
+    let mut end = hittable_range.end();
+
+    for axis in 0..3 {
+      let inverse_direction = 1.0 / ray.direction().axis(axis);
+      let origin = ray.origin().axis(axis);
+
+      let mut near = (self.min.axis(axis) - origin) * inverse_direction;
+      let mut far = (self.max.axis(axis) - origin) * inverse_direction;
+
+      if inverse_direction < 0.0 {
+        std::mem::swap(&mut near, &mut far);
+      }
+
+      start = near.max(start);
+      end = far.min(end);
+
+      if end < start {
+        return false;
+      }
+    }
+
+    true
+  }
+}
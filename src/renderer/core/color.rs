@@ -11,15 +11,41 @@ pub fn linear_blend(first: Color, second: Color, fraction: f64) -> Color {
   (fraction * first) + ((1.0 - fraction) * second)
 }
 
-fn gamma_correct(value: f64) -> f64 {
-  value.sqrt()
+/// The relative luminance of a linear Rec. 709 color.
+pub fn luminance(color: Color) -> f64 {
+  let (red, green, blue) = color.tuple();
+  (0.2126 * red) + (0.7152 * green) + (0.0722 * blue)
 }
 
-pub fn calculate_color(color: Color, samples: usize) -> Color {
-  color.map(|component| 255.0 * gamma_correct(component / samples as f64))
+/// Colours `fraction` in [0, 1] from blue through green to red, for
+/// visualising quantities as images.
+pub fn heatmap(fraction: f64) -> Color {
+  let fraction = fraction.clamp(0.0, 1.0);
+
+  if fraction < 0.5 {
+    linear_blend(COLOR_GREEN, COLOR_BLUE, 2.0 * fraction)
+  } else {
+    linear_blend(COLOR_RED, COLOR_GREEN, (2.0 * fraction) - 1.0)
+  }
 }
 
-pub fn print_color(color: Color) {
-  let (red, green, blue) = color.tuple();
-  println!("{} {} {}", red as usize, green as usize, blue as usize);
+/// Applies the sRGB transfer function to a linear value in [0, 1].
+fn srgb_encode(value: f64) -> f64 {
+  if value <= 0.0031308 {
+    12.92 * value
+  } else {
+    (1.055 * value.powf(1.0 / 2.4)) - 0.055
+  }
+}
+
+/// Encodes a linear `color` as sRGB in [0, 255]. Values outside the
+/// displayable range are clamped.
+pub fn calculate_color(color: Color) -> Color {
+  color.map(|component| 255.0 * srgb_encode(component.clamp(0.0, 1.0)))
+}
+
+/// Quantizes a linear color to 8-bit sRGB.
+pub fn to_rgb8(color: Color) -> [u8; 3] {
+  let (red, green, blue) = calculate_color(color).tuple();
+  [red, green, blue].map(|component| component.round() as u8)
 }
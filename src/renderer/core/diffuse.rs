@@ -1,17 +1,31 @@
 use crate::renderer::core::vec3::Vec3;
+use crate::renderer::samplers::sampler::Sampler;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
 
-pub fn random_point_in_unit_sphere() -> Vec3 {
-  loop {
-    let vector = Vec3::random(-1.0..=1.0);
+pub fn random_point_in_unit_sphere(sampler: &mut dyn Sampler) -> Vec3 {
+  random_point_on_unit_sphere(sampler) * sampler.get_1d().cbrt()
+}
 
-    if vector.length_squared() < 1.0 {
-      return vector;
-    }
+/// Maps a sample onto the disk concentrically (Shirley and Chiu), which keeps
+/// evenly spread samples evenly spread.
+pub fn random_point_in_unit_disk(sampler: &mut dyn Sampler) -> Vec3 {
+  let (u, v) = sampler.get_2d();
+  let (x, y) = ((2.0 * u) - 1.0, (2.0 * v) - 1.0);
+
+  if x == 0.0 && y == 0.0 {
+    return Vec3::default();
   }
+
+  let (radius, angle) = if x.abs() > y.abs() {
+    (x, FRAC_PI_4 * (y / x))
+  } else {
+    (y, FRAC_PI_2 - (FRAC_PI_4 * (x / y)))
+  };
+  Vec3::new(radius * angle.cos(), radius * angle.sin(), 0.0)
 }
 
-fn random_point_in_normal_hemisphere(normal: &Vec3) -> Vec3 {
-  let point = random_point_in_unit_sphere();
+fn random_point_in_normal_hemisphere(normal: &Vec3, sampler: &mut dyn Sampler) -> Vec3 {
+  let point = random_point_in_unit_sphere(sampler);
 
   if point.dot(normal) > 0.0 {
     point
@@ -20,8 +34,13 @@ fn random_point_in_normal_hemisphere(normal: &Vec3) -> Vec3 {
   }
 }
 
-fn random_point_on_unit_sphere() -> Vec3 {
-  random_point_in_unit_sphere().unit()
+pub fn random_point_on_unit_sphere(sampler: &mut dyn Sampler) -> Vec3 {
+  let (u, v) = sampler.get_2d();
+  let z = 1.0 - (2.0 * u);
+  let radius = (1.0 - (z * z)).max(0.0).sqrt();
+  let phi = 2.0 * PI * v;
+
+  Vec3::new(radius * phi.cos(), radius * phi.sin(), z)
 }
 
 #[allow(dead_code)]
@@ -31,12 +50,14 @@ pub enum DiffuseMethod {
   TrueLambertian,
 }
 
-pub fn bounce_direction(normal: &Vec3, method: DiffuseMethod) -> Vec3 {
+pub fn bounce_direction(normal: &Vec3, method: DiffuseMethod, sampler: &mut dyn Sampler) -> Vec3 {
   match method {
-    DiffuseMethod::BounceInHemisphere => random_point_in_normal_hemisphere(normal),
+    DiffuseMethod::BounceInHemisphere => random_point_in_normal_hemisphere(normal, sampler),
     DiffuseMethod::LambertianApproximate => {
-      *(*normal + random_point_in_unit_sphere()).non_zero_or(normal)
+      *(*normal + random_point_in_unit_sphere(sampler)).non_zero_or(normal)
+    }
+    DiffuseMethod::TrueLambertian => {
+      *(*normal + random_point_on_unit_sphere(sampler)).non_zero_or(normal)
     }
-    DiffuseMethod::TrueLambertian => *(*normal + random_point_on_unit_sphere()).non_zero_or(normal),
   }
 }
@@ -0,0 +1,113 @@
+/// A piecewise-constant distribution over [0, 1), with one step per function
+/// value.
+pub struct Distribution1D {
+  function: Vec<f64>,
+  cdf: Vec<f64>,
+  integral: f64,
+}
+
+impl Distribution1D {
+  /// Builds the distribution proportional to `function`, whose values must not
+  /// be negative. A function which is zero everywhere is sampled uniformly.
+  pub fn new(function: Vec<f64>) -> Self {
+    let count = function.len() as f64;
+
+    let mut cdf = Vec::with_capacity(function.len() + 1);
+    cdf.push(0.0);
+    for value in &function {
+      cdf.push(cdf.last().unwrap() + (value / count));
+    }
+
+    let integral = *cdf.last().unwrap();
+    for (index, step) in cdf.iter_mut().enumerate() {
+      *step = if integral > 0.0 {
+        *step / integral
+      } else {
+        index as f64 / count
+      };
+    }
+
+    Self {
+      function,
+      cdf,
+      integral,
+    }
+  }
+
+  pub fn count(&self) -> usize {
+    self.function.len()
+  }
+
+  /// The average of the function over [0, 1).
+  pub fn integral(&self) -> f64 {
+    self.integral
+  }
+
+  /// Maps a uniform `sample` in [0, 1) to a point in [0, 1), returning the
+  /// point, its density and the index of the step it lies in.
+  pub fn sample(&self, sample: f64) -> (f64, f64, usize) {
+    // The last step whose start is at or before the sample.
+    let index = (self.cdf.partition_point(|&step| step <= sample) - 1).min(self.count() - 1);
+
+    let width = self.cdf[index + 1] - self.cdf[index];
+    let offset = if width > 0.0 {
+      (sample - self.cdf[index]) / width
+    } else {
+      0.0
+    };
+
+    (
+      (index as f64 + offset) / self.count() as f64,
+      self.pdf(index),
+      index,
+    )
+  }
+
+  /// The density of points within step `index`.
+  pub fn pdf(&self, index: usize) -> f64 {
+    if self.integral > 0.0 {
+      self.function[index] / self.integral
+    } else {
+      1.0
+    }
+  }
+}
+
+/// A piecewise-constant distribution over [0, 1)², sampled by picking a row
+/// from the marginal distribution and then a column within the row.
+pub struct Distribution2D {
+  rows: Vec<Distribution1D>,
+  marginal: Distribution1D,
+}
+
+impl Distribution2D {
+  /// Builds the distribution proportional to `function`, given as `width`
+  /// values per row, starting from the top.
+  pub fn new(function: &[f64], width: usize) -> Self {
+    let rows = function
+      .chunks_exact(width)
+      .map(|row| Distribution1D::new(row.to_vec()))
+      .collect::<Vec<_>>();
+    let marginal = Distribution1D::new(rows.iter().map(Distribution1D::integral).collect());
+
+    Self { rows, marginal }
+  }
+
+  /// Maps uniform samples to a point (x, y) in [0, 1)², returning the point
+  /// and its density.
+  pub fn sample(&self, samples: (f64, f64)) -> ((f64, f64), f64) {
+    let (y, row_pdf, row) = self.marginal.sample(samples.1);
+    let (x, column_pdf, _) = self.rows[row].sample(samples.0);
+
+    ((x, y), row_pdf * column_pdf)
+  }
+
+  /// The density of the point (x, y) in [0, 1)².
+  pub fn pdf(&self, (x, y): (f64, f64)) -> f64 {
+    let row = ((y * self.marginal.count() as f64) as usize).min(self.marginal.count() - 1);
+    let columns = &self.rows[row];
+    let column = ((x * columns.count() as f64) as usize).min(columns.count() - 1);
+
+    self.marginal.pdf(row) * columns.pdf(column)
+  }
+}
@@ -27,6 +27,14 @@ impl<T: Mathematical> Range<T> {
     Range { start, end }
   }
 
+  pub fn start(&self) -> T {
+    self.start
+  }
+
+  pub fn end(&self) -> T {
+    self.end
+  }
+
   pub fn dist(&self) -> T {
     self.end - self.start
   }
@@ -0,0 +1,37 @@
+use crate::renderer::core::vec3::Vec3;
+
+/// An orthonormal basis, for working in coordinates local to a direction `w`.
+pub struct Onb {
+  u: Vec3,
+  v: Vec3,
+  w: Vec3,
+}
+
+impl Onb {
+  pub fn from_w(w: &Vec3) -> Self {
+    let w = w.unit();
+    let helper = if w.x().abs() > 0.9 {
+      Vec3::new(0.0, 1.0, 0.0)
+    } else {
+      Vec3::new(1.0, 0.0, 0.0)
+    };
+    let v = w.cross(&helper).unit();
+    let u = w.cross(&v);
+
+    Self { u, v, w }
+  }
+
+  /// Converts local coordinates (u, v, w) to a world space vector.
+  pub fn local(&self, u: f64, v: f64, w: f64) -> Vec3 {
+    (u * self.u) + (v * self.v) + (w * self.w)
+  }
+
+  /// Converts a world space vector to local coordinates (u, v, w).
+  pub fn to_local(&self, vector: &Vec3) -> Vec3 {
+    Vec3::new(
+      vector.dot(&self.u),
+      vector.dot(&self.v),
+      vector.dot(&self.w),
+    )
+  }
+}
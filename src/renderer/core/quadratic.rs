@@ -44,14 +44,29 @@ impl Quadratic {
     (-self.b + (multiplier * self.discriminant().sqrt())) / (2.0 * self.a)
   }
 
+  /// The real roots in ascending order. Equations without a squared term have
+  /// at most the one root of the linear equation left.
   pub fn find_real_roots(&self) -> Vec<f64> {
-    match self.roots_type() {
+    if self.a == 0.0 {
+      return if self.b == 0.0 {
+        vec![]
+      } else {
+        vec![-self.c / self.b]
+      };
+    }
+
+    let mut roots = match self.roots_type() {
       Roots::TwoComplex => vec![],
       Roots::OneReal => vec![self.find_root(DisriminantRoot::Positive)],
       Roots::TwoReal => vec![
         self.find_root(DisriminantRoot::Negative),
         self.find_root(DisriminantRoot::Positive),
       ],
+    };
+
+    if self.a < 0.0 {
+      roots.reverse();
     }
+    roots
   }
 }
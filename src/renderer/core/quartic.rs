@@ -0,0 +1,180 @@
+/// The most steps taken to narrow down each root.
+const MAX_ITERATIONS: usize = 100;
+
+/// Evaluates the polynomial with `coefficients`, highest degree first.
+fn evaluate(coefficients: &[f64], x: f64) -> f64 {
+  coefficients
+    .iter()
+    .fold(0.0, |value, coefficient| (value * x) + coefficient)
+}
+
+fn derivative(coefficients: &[f64]) -> Vec<f64> {
+  let degree = coefficients.len() - 1;
+
+  coefficients[..degree]
+    .iter()
+    .enumerate()
+    .map(|(index, coefficient)| (degree - index) as f64 * coefficient)
+    .collect()
+}
+
+/// Narrows down the root of `coefficients` between `low` and `high`, where the
+/// polynomial changes sign, with Newton's method, falling back on bisection
+/// whenever a Newton step would leave the bracket.
+fn refine(coefficients: &[f64], slope: &[f64], mut low: f64, mut high: f64) -> f64 {
+  let rising = evaluate(coefficients, low) < 0.0;
+  let mut x = 0.5 * (low + high);
+
+  for _ in 0..MAX_ITERATIONS {
+    let value = evaluate(coefficients, x);
+    if value == 0.0 {
+      return x;
+    }
+
+    if (value < 0.0) == rising {
+      low = x;
+    } else {
+      high = x;
+    }
+
+    let newton = x - (value / evaluate(slope, x));
+    let next = if (low < newton) && (newton < high) {
+      newton
+    } else {
+      0.5 * (low + high)
+    };
+
+    if (next - x).abs() <= f64::EPSILON * x.abs().max(1.0) {
+      return next;
+    }
+    x = next;
+  }
+
+  x
+}
+
+/// The real roots of the polynomial with `coefficients`, highest degree first,
+/// in ascending order. Between consecutive turning points (the roots of the
+/// derivative) the polynomial is monotonic, so each span holds at most one
+/// root, found wherever the polynomial changes sign across it. Repeated roots
+/// which only touch zero are missed; for rays these are grazing hits.
+fn real_roots(coefficients: &[f64]) -> Vec<f64> {
+  let start = coefficients
+    .iter()
+    .position(|coefficient| *coefficient != 0.0)
+    .unwrap_or(coefficients.len());
+  let coefficients = &coefficients[start..];
+
+  match coefficients.len() {
+    0 | 1 => return vec![],
+    2 => return vec![-coefficients[1] / coefficients[0]],
+    _ => {}
+  }
+
+  // Cauchy's bound on the size of the roots.
+  let bound = 1.0
+    + coefficients[1..]
+      .iter()
+      .map(|coefficient| (coefficient / coefficients[0]).abs())
+      .fold(0.0, f64::max);
+
+  let slope = derivative(coefficients);
+  let mut points = vec![-bound];
+  points.extend(
+    real_roots(&slope)
+      .into_iter()
+      .filter(|point| point.abs() < bound),
+  );
+  points.push(bound);
+
+  points
+    .windows(2)
+    .filter_map(|span| {
+      let (low, high) = (span[0], span[1]);
+      let (low_value, high_value) = (evaluate(coefficients, low), evaluate(coefficients, high));
+
+      // A root at the end of the span is found by the next one, which starts there.
+      if low_value == 0.0 {
+        Some(low)
+      } else if high_value == 0.0 {
+        None
+      } else if (low_value < 0.0) != (high_value < 0.0) {
+        Some(refine(coefficients, &slope, low, high))
+      } else {
+        None
+      }
+    })
+    .collect()
+}
+
+/// The equation a x^4 + b x^3 + c x^2 + d x + e = 0.
+pub struct Quartic {
+  coefficients: [f64; 5],
+}
+
+impl Quartic {
+  pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64) -> Self {
+    Self {
+      coefficients: [a, b, c, d, e],
+    }
+  }
+
+  /// The real roots in ascending order.
+  pub fn find_real_roots(&self) -> Vec<f64> {
+    real_roots(&self.coefficients)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::Quartic;
+
+  fn assert_roots(quartic: Quartic, expected: &[f64]) {
+    let roots = quartic.find_real_roots();
+
+    assert_eq!(
+      roots.len(),
+      expected.len(),
+      "roots {roots:?}, expected {expected:?}"
+    );
+    for (root, expected) in roots.iter().zip(expected) {
+      assert!(
+        (root - expected).abs() < 1e-9,
+        "roots {roots:?}, expected {expected:?}"
+      );
+    }
+  }
+
+  #[test]
+  fn four_distinct_roots() {
+    // (x + 2)(x + 1)(x - 1)(x - 3)
+    assert_roots(
+      Quartic::new(1.0, -1.0, -7.0, 1.0, 6.0),
+      &[-2.0, -1.0, 1.0, 3.0],
+    );
+  }
+
+  #[test]
+  fn double_root() {
+    // (x + 1)^2 (x - 2)(x - 4), with the double root at a turning point.
+    assert_roots(Quartic::new(1.0, -4.0, -3.0, 10.0, 8.0), &[-1.0, 2.0, 4.0]);
+  }
+
+  #[test]
+  fn root_at_a_turning_point_is_reported_once() {
+    // x^2 (x - 1)(x + 1) touches zero at 0, which is also a turning point.
+    assert_roots(Quartic::new(1.0, 0.0, -1.0, 0.0, 0.0), &[-1.0, 0.0, 1.0]);
+  }
+
+  #[test]
+  fn no_real_roots() {
+    // (x^2 + 1)(x^2 + 4)
+    assert_roots(Quartic::new(1.0, 0.0, 5.0, 0.0, 4.0), &[]);
+  }
+
+  #[test]
+  fn cubic_when_leading_coefficient_is_zero() {
+    // (x + 1)(x - 2)(x - 5)
+    assert_roots(Quartic::new(0.0, 1.0, -6.0, 3.0, 10.0), &[-1.0, 2.0, 5.0]);
+  }
+}
@@ -1,6 +1,97 @@
-use rand::distributions::uniform::SampleRange;
-use rand::Rng;
+/// Scrambles the bits of `value` (SplitMix64's finaliser), so that nearby
+/// inputs give unrelated outputs.
+fn mix(mut value: u64) -> u64 {
+  value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+  value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+  value ^ (value >> 31)
+}
+
+/// Combines a seed with a value, such as a pixel index or sample dimension,
+/// into an unrelated seed.
+pub fn hash(seed: u64, value: u64) -> u64 {
+  mix(seed ^ mix(value))
+}
+
+/// Element `index` of a random permutation of `0..length` chosen by `seed`
+/// (Kensler's "Correlated Multi-Jittered Sampling"), without storing it.
+pub fn permutation_element(mut index: u32, length: u32, seed: u32) -> u32 {
+  let mut mask = length - 1;
+  mask |= mask >> 1;
+  mask |= mask >> 2;
+  mask |= mask >> 4;
+  mask |= mask >> 8;
+  mask |= mask >> 16;
+
+  loop {
+    index ^= seed;
+    index = index.wrapping_mul(0xe170893d);
+    index ^= seed >> 16;
+    index ^= (index & mask) >> 4;
+    index ^= seed >> 8;
+    index = index.wrapping_mul(0x0929eb3f);
+    index ^= seed >> 23;
+    index ^= (index & mask) >> 1;
+    index = index.wrapping_mul(1 | (seed >> 27));
+    index = index.wrapping_mul(0x6935fa69);
+    index ^= (index & mask) >> 11;
+    index = index.wrapping_mul(0x74dcb303);
+    index ^= (index & mask) >> 2;
+    index = index.wrapping_mul(0x9e501cc3);
+    index ^= (index & mask) >> 2;
+    index = index.wrapping_mul(0xc860a3df);
+    index &= mask;
+    index ^= index >> 5;
+
+    if index < length {
+      return index.wrapping_add(seed) % length;
+    }
+  }
+}
+
+/// A small, fast PCG (XSH RR) random number generator. Each sample of each
+/// pixel gets its own generator, so that a render's noise only depends on its
+/// seed, not on how the work was split between threads.
+#[derive(Clone)]
+pub struct Pcg32 {
+  state: u64,
+  increment: u64,
+}
+
+impl Pcg32 {
+  const MULTIPLIER: u64 = 6364136223846793005;
+
+  /// Creates a generator from a starting state and one of 2^63 independent
+  /// streams.
+  pub fn new(seed: u64, stream: u64) -> Self {
+    let mut rng = Self {
+      state: 0,
+      increment: (stream << 1) | 1,
+    };
+    rng.next_u32();
+    rng.state = rng.state.wrapping_add(seed);
+    rng.next_u32();
+
+    rng
+  }
+
+  /// The generator for sample `sample` of the pixel with index `pixel`.
+  pub fn for_sample(seed: u64, pixel: usize, sample: usize) -> Self {
+    Self::new(hash(seed, pixel as u64), sample as u64)
+  }
+
+  pub fn next_u32(&mut self) -> u32 {
+    let state = self.state;
+    self.state = state
+      .wrapping_mul(Self::MULTIPLIER)
+      .wrapping_add(self.increment);
+
+    let xorshifted = (((state >> 18) ^ state) >> 27) as u32;
+    xorshifted.rotate_right((state >> 59) as u32)
+  }
 
-pub fn random(range: impl SampleRange<f64>) -> f64 {
-  rand::thread_rng().gen_range(range)
+  /// A uniform number in [0, 1), with 53 random bits.
+  pub fn next_f64(&mut self) -> f64 {
+    let bits = ((self.next_u32() as u64) << 21) ^ (self.next_u32() as u64);
+    (bits & ((1 << 53) - 1)) as f64 / (1u64 << 53) as f64
+  }
 }
@@ -1,6 +1,6 @@
 use crate::renderer::core::vec3::{Point3, Vec3};
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 pub struct Ray {
   origin: Point3,
   direction: Vec3,
@@ -0,0 +1,78 @@
+use crate::renderer::core::color;
+use crate::renderer::core::vec3::Color;
+use std::io::{self, Write};
+
+/// The running sum of a pixel's samples, and the variance of their luminance
+/// by Welford's algorithm.
+#[derive(Default, Clone)]
+pub struct RunningStats {
+  count: usize,
+  sum: Color,
+  luminance_mean: f64,
+  luminance_m2: f64,
+}
+
+impl RunningStats {
+  /// The size of the stats as written by `write`.
+  pub const BYTES: usize = 48;
+
+  pub fn add(&mut self, sample: Color) {
+    self.count += 1;
+    self.sum += sample;
+
+    let luminance = color::luminance(sample);
+    let delta = luminance - self.luminance_mean;
+    self.luminance_mean += delta / self.count as f64;
+    self.luminance_m2 += delta * (luminance - self.luminance_mean);
+  }
+
+  pub fn count(&self) -> usize {
+    self.count
+  }
+
+  pub fn mean(&self) -> Color {
+    self.sum / self.count as f64
+  }
+
+  /// The standard error of the mean luminance, relative to the mean. Zero for
+  /// samples which all agree, such as a pixel which only sees black.
+  pub fn relative_error(&self) -> f64 {
+    if self.count < 2 {
+      return f64::INFINITY;
+    }
+
+    let variance = self.luminance_m2 / (self.count - 1) as f64;
+    let error = (variance / self.count as f64).sqrt();
+
+    if error == 0.0 {
+      0.0
+    } else {
+      error / self.luminance_mean.abs()
+    }
+  }
+
+  /// Writes the stats exactly, as little-endian numbers.
+  pub fn write(&self, output: &mut impl Write) -> io::Result<()> {
+    let (red, green, blue) = self.sum.tuple();
+
+    output.write_all(&(self.count as u64).to_le_bytes())?;
+    for value in [red, green, blue, self.luminance_mean, self.luminance_m2] {
+      output.write_all(&value.to_le_bytes())?;
+    }
+
+    Ok(())
+  }
+
+  /// Reads stats written by `write` from the first `BYTES` of `bytes`.
+  pub fn read(bytes: &[u8]) -> Self {
+    let word = |index: usize| -> [u8; 8] { bytes[index * 8..(index + 1) * 8].try_into().unwrap() };
+    let value = |index: usize| f64::from_le_bytes(word(index));
+
+    Self {
+      count: u64::from_le_bytes(word(0)) as usize,
+      sum: Color::new(value(1), value(2), value(3)),
+      luminance_mean: value(4),
+      luminance_m2: value(5),
+    }
+  }
+}
@@ -0,0 +1,229 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec3::{Point3, Vec3};
+
+type Matrix = [[f64; 4]; 4];
+
+const IDENTITY: Matrix = [
+  [1.0, 0.0, 0.0, 0.0],
+  [0.0, 1.0, 0.0, 0.0],
+  [0.0, 0.0, 1.0, 0.0],
+  [0.0, 0.0, 0.0, 1.0],
+];
+
+fn multiply(left: &Matrix, right: &Matrix) -> Matrix {
+  let mut product = [[0.0; 4]; 4];
+
+  for (row, product_row) in product.iter_mut().enumerate() {
+    for (column, value) in product_row.iter_mut().enumerate() {
+      *value = (0..4).map(|k| left[row][k] * right[k][column]).sum();
+    }
+  }
+
+  product
+}
+
+fn transpose(matrix: &Matrix) -> Matrix {
+  let mut transposed = [[0.0; 4]; 4];
+
+  for (row, values) in matrix.iter().enumerate() {
+    for (column, value) in values.iter().enumerate() {
+      transposed[column][row] = *value;
+    }
+  }
+
+  transposed
+}
+
+/// An affine transformation of space as a 4x4 matrix, kept together with its
+/// inverse so that neither has to be computed from the other.
+#[derive(Debug, Clone, Copy)]
+pub struct Transform {
+  matrix: Matrix,
+  inverse: Matrix,
+}
+
+impl Default for Transform {
+  fn default() -> Self {
+    Self::identity()
+  }
+}
+
+impl Transform {
+  pub const fn identity() -> Self {
+    Self {
+      matrix: IDENTITY,
+      inverse: IDENTITY,
+    }
+  }
+
+  pub fn translate(offset: &Vec3) -> Self {
+    let (x, y, z) = offset.tuple();
+
+    Self {
+      matrix: [
+        [1.0, 0.0, 0.0, x],
+        [0.0, 1.0, 0.0, y],
+        [0.0, 0.0, 1.0, z],
+        [0.0, 0.0, 0.0, 1.0],
+      ],
+      inverse: [
+        [1.0, 0.0, 0.0, -x],
+        [0.0, 1.0, 0.0, -y],
+        [0.0, 0.0, 1.0, -z],
+        [0.0, 0.0, 0.0, 1.0],
+      ],
+    }
+  }
+
+  /// Scales each axis by the matching component of `factors`, none of which
+  /// may be zero.
+  pub fn scale(factors: &Vec3) -> Self {
+    let (x, y, z) = factors.tuple();
+
+    Self {
+      matrix: [
+        [x, 0.0, 0.0, 0.0],
+        [0.0, y, 0.0, 0.0],
+        [0.0, 0.0, z, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+      ],
+      inverse: [
+        [1.0 / x, 0.0, 0.0, 0.0],
+        [0.0, 1.0 / y, 0.0, 0.0],
+        [0.0, 0.0, 1.0 / z, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+      ],
+    }
+  }
+
+  /// Rotates by `degrees` around `axis`, anticlockwise when looking down the
+  /// axis towards the origin.
+  pub fn rotate(axis: &Vec3, degrees: f64) -> Self {
+    let (x, y, z) = axis.unit().tuple();
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let complement = 1.0 - cos;
+
+    let matrix = [
+      [
+        (x * x * complement) + cos,
+        (x * y * complement) - (z * sin),
+        (x * z * complement) + (y * sin),
+        0.0,
+      ],
+      [
+        (y * x * complement) + (z * sin),
+        (y * y * complement) + cos,
+        (y * z * complement) - (x * sin),
+        0.0,
+      ],
+      [
+        (z * x * complement) - (y * sin),
+        (z * y * complement) + (x * sin),
+        (z * z * complement) + cos,
+        0.0,
+      ],
+      [0.0, 0.0, 0.0, 1.0],
+    ];
+
+    // Rotations are orthogonal, so their inverse is their transpose.
+    Self {
+      matrix,
+      inverse: transpose(&matrix),
+    }
+  }
+
+  /// The transform which applies this one and then `next`.
+  pub fn then(&self, next: &Transform) -> Self {
+    Self {
+      matrix: multiply(&next.matrix, &self.matrix),
+      inverse: multiply(&self.inverse, &next.inverse),
+    }
+  }
+
+  pub fn inverse(&self) -> Self {
+    Self {
+      matrix: self.inverse,
+      inverse: self.matrix,
+    }
+  }
+
+  pub fn point(&self, point: &Point3) -> Point3 {
+    let m = &self.matrix;
+    let (x, y, z) = point.tuple();
+
+    Point3::new(
+      (m[0][0] * x) + (m[0][1] * y) + (m[0][2] * z) + m[0][3],
+      (m[1][0] * x) + (m[1][1] * y) + (m[1][2] * z) + m[1][3],
+      (m[2][0] * x) + (m[2][1] * y) + (m[2][2] * z) + m[2][3],
+    )
+  }
+
+  /// Transforms a direction, which unlike a point is not translated.
+  pub fn vector(&self, vector: &Vec3) -> Vec3 {
+    let m = &self.matrix;
+    let (x, y, z) = vector.tuple();
+
+    Vec3::new(
+      (m[0][0] * x) + (m[0][1] * y) + (m[0][2] * z),
+      (m[1][0] * x) + (m[1][1] * y) + (m[1][2] * z),
+      (m[2][0] * x) + (m[2][1] * y) + (m[2][2] * z),
+    )
+  }
+
+  /// Transforms a surface normal by the inverse transpose, which keeps it
+  /// perpendicular to the transformed surface. The result is not unit length.
+  pub fn normal(&self, normal: &Vec3) -> Vec3 {
+    let m = &self.inverse;
+    let (x, y, z) = normal.tuple();
+
+    Vec3::new(
+      (m[0][0] * x) + (m[1][0] * y) + (m[2][0] * z),
+      (m[0][1] * x) + (m[1][1] * y) + (m[2][1] * z),
+      (m[0][2] * x) + (m[1][2] * y) + (m[2][2] * z),
+    )
+  }
+
+  /// Transforms a ray, keeping the times along it of the points it passes
+  /// through.
+  pub fn ray(&self, ray: &Ray) -> Ray {
+    Ray::new(self.point(&ray.origin()), self.vector(&ray.direction()))
+  }
+
+  /// The box enclosing the transformed `aabb`.
+  pub fn bounding_box(&self, aabb: &Aabb) -> Aabb {
+    let (min, max) = (aabb.min(), aabb.max());
+    let corners = (0..8)
+      .map(|corner| {
+        self.point(&Point3::new(
+          if corner & 1 == 0 { min.x() } else { max.x() },
+          if corner & 2 == 0 { min.y() } else { max.y() },
+          if corner & 4 == 0 { min.z() } else { max.z() },
+        ))
+      })
+      .collect::<Vec<_>>();
+
+    Aabb::from_points(&corners)
+  }
+
+  /// Whether the transform only rotates, reflects, translates and scales
+  /// uniformly, which keeps the angles between directions.
+  pub fn is_similarity(&self) -> bool {
+    let columns = [0, 1, 2].map(|column| {
+      Vec3::new(
+        self.matrix[0][column],
+        self.matrix[1][column],
+        self.matrix[2][column],
+      )
+    });
+    let scale = columns[0].length_squared();
+    let close = |value: f64, expected: f64| (value - expected).abs() <= 1e-9 * scale;
+
+    (0..3).all(|i| {
+      (0..3).all(|j| {
+        let expected = if i == j { scale } else { 0.0 };
+        close(columns[i].dot(&columns[j]), expected)
+      })
+    })
+  }
+}
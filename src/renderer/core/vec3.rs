@@ -1,5 +1,4 @@
-use crate::renderer::core::{math, random};
-use rand::distributions::uniform::SampleRange;
+use crate::renderer::core::math;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -21,17 +20,6 @@ impl Vec3 {
     Vec3::new(component, component, component)
   }
 
-  pub fn random<T>(range: T) -> Self
-  where
-    T: SampleRange<f64> + Clone,
-  {
-    Vec3::new(
-      random::random(range.clone()),
-      random::random(range.clone()),
-      random::random(range),
-    )
-  }
-
   pub const fn x(&self) -> f64 {
     self.x
   }
@@ -44,6 +32,17 @@ impl Vec3 {
     self.z
   }
 
+  /// Returns the component along `axis`, where 0, 1 and 2 are the x, y and z
+  /// axes.
+  pub fn axis(&self, axis: usize) -> f64 {
+    match axis {
+      0 => self.x(),
+      1 => self.y(),
+      2 => self.z(),
+      _ => panic!("Vec3 has no axis {axis}."),
+    }
+  }
+
   pub fn tuple(&self) -> (f64, f64, f64) {
     (self.x(), self.y(), self.z())
   }
@@ -82,6 +81,22 @@ impl Vec3 {
     Vec3::new(func(self.x()), func(self.y()), func(self.z()))
   }
 
+  pub fn zip(&self, other: &Vec3, func: impl Fn(f64, f64) -> f64) -> Vec3 {
+    Vec3::new(
+      func(self.x(), other.x()),
+      func(self.y(), other.y()),
+      func(self.z(), other.z()),
+    )
+  }
+
+  pub fn min(&self, other: &Vec3) -> Vec3 {
+    self.zip(other, f64::min)
+  }
+
+  pub fn max(&self, other: &Vec3) -> Vec3 {
+    self.zip(other, f64::max)
+  }
+
   pub fn all(&self, func: impl Fn(f64) -> bool) -> bool {
     func(self.x()) && func(self.y()) && func(self.z())
   }
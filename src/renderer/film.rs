@@ -0,0 +1,116 @@
+use crate::renderer::core::statistics::RunningStats;
+use crate::renderer::core::vec2::Vec2;
+use crate::renderer::core::vec3::Color;
+
+/// The samples gathered for every pixel of the image, ordered from the top
+/// left.
+pub struct Film {
+  size: Vec2<usize>,
+  /// The number of samples every pixel has been given the chance to take.
+  samples: usize,
+  pixels: Vec<RunningStats>,
+}
+
+/// A rectangle of the film which is rendered as one piece of work, into its
+/// own pixels.
+pub struct Tile {
+  origin: Vec2<usize>,
+  size: Vec2<usize>,
+  pixels: Vec<RunningStats>,
+}
+
+impl Film {
+  pub fn new(size: Vec2<usize>) -> Self {
+    Self::from_pixels(size, 0, vec![RunningStats::default(); size.x() * size.y()])
+  }
+
+  pub fn from_pixels(size: Vec2<usize>, samples: usize, pixels: Vec<RunningStats>) -> Self {
+    debug_assert_eq!(pixels.len(), size.x() * size.y());
+
+    Self {
+      size,
+      samples,
+      pixels,
+    }
+  }
+
+  pub fn size(&self) -> Vec2<usize> {
+    self.size
+  }
+
+  pub fn samples(&self) -> usize {
+    self.samples
+  }
+
+  pub fn set_samples(&mut self, samples: usize) {
+    self.samples = samples;
+  }
+
+  pub fn pixels(&self) -> &[RunningStats] {
+    &self.pixels
+  }
+
+  /// Splits the film into tiles of at most `tile_size` pixels across, row by
+  /// row from the top left, holding copies of the film's pixels.
+  pub fn tiles(&self, tile_size: usize) -> Vec<Tile> {
+    let (width, height) = (self.size.x(), self.size.y());
+
+    (0..height)
+      .step_by(tile_size)
+      .flat_map(|y| {
+        (0..width).step_by(tile_size).map(move |x| {
+          let size = Vec2::new(tile_size.min(width - x), tile_size.min(height - y));
+          let pixels = (y..y + size.y())
+            .flat_map(|row| {
+              let start = (row * width) + x;
+              self.pixels[start..start + size.x()].iter().cloned()
+            })
+            .collect();
+
+          Tile {
+            origin: Vec2::new(x, y),
+            size,
+            pixels,
+          }
+        })
+      })
+      .collect()
+  }
+
+  /// Copies the pixels of a rendered `tile` into the film.
+  pub fn add_tile(&mut self, tile: &Tile) {
+    for (row, pixels) in tile.pixels.chunks(tile.size.x()).enumerate() {
+      let start = ((tile.origin.y() + row) * self.size.x()) + tile.origin.x();
+      self.pixels[start..start + pixels.len()].clone_from_slice(pixels);
+    }
+  }
+
+  /// The mean linear radiance of every pixel.
+  pub fn colors(&self) -> Vec<Color> {
+    self.pixels.iter().map(RunningStats::mean).collect()
+  }
+
+  /// The number of samples taken for every pixel.
+  pub fn sample_counts(&self) -> Vec<usize> {
+    self.pixels.iter().map(RunningStats::count).collect()
+  }
+}
+
+impl Tile {
+  /// The pixels of the tile with their positions in the film, counted from
+  /// the top left.
+  pub fn pixels_mut(&mut self) -> impl Iterator<Item = (Vec2<usize>, &mut RunningStats)> {
+    let (origin, width) = (self.origin, self.size.x());
+
+    self
+      .pixels
+      .iter_mut()
+      .enumerate()
+      .map(move |(index, pixel)| {
+        (
+          Vec2::new(origin.x() + (index % width), origin.y() + (index / width)),
+          pixel,
+        )
+      })
+  }
+}
@@ -0,0 +1,7 @@
+pub mod ambient_occlusion;
+pub mod debug;
+pub mod direct;
+pub mod integrator;
+mod lighting;
+pub mod path;
+pub mod whitted;
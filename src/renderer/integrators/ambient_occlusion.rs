@@ -0,0 +1,43 @@
+use crate::renderer::core::color::COLOR_WHITE;
+use crate::renderer::core::diffuse::{self, DiffuseMethod};
+use crate::renderer::core::math::Range;
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec3::Color;
+use crate::renderer::integrators::integrator::Integrator;
+use crate::renderer::integrators::lighting;
+use crate::renderer::render::{RenderParams, Scene};
+use crate::renderer::samplers::sampler::Sampler;
+
+/// Shades surfaces by how much of the hemisphere above them is open, weighted
+/// by the cosine to the normal. Only surfaces within the render's occlusion
+/// distance count as occluders.
+pub struct AmbientOcclusion;
+
+impl Integrator for AmbientOcclusion {
+  fn radiance(
+    &self,
+    ray: &Ray,
+    scene: &Scene,
+    params: &RenderParams,
+    sampler: &mut dyn Sampler,
+  ) -> Color {
+    let Some(hit) = scene.world.hit(ray, lighting::hittable_range()) else {
+      return COLOR_WHITE;
+    };
+
+    // Cosine-weighted directions make the estimate just the visibility.
+    let direction =
+      diffuse::bounce_direction(&hit.normal(), DiffuseMethod::TrueLambertian, sampler);
+    let occlusion_ray = Ray::new(hit.point(), direction.unit());
+    let range = Range::new(
+      lighting::hittable_range().start(),
+      params.occlusion_distance,
+    );
+
+    if scene.world.hit(&occlusion_ray, range).is_some() {
+      Color::default()
+    } else {
+      COLOR_WHITE
+    }
+  }
+}
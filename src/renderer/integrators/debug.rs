@@ -0,0 +1,46 @@
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec3::{Color, Vec3};
+use crate::renderer::integrators::integrator::Integrator;
+use crate::renderer::integrators::lighting;
+use crate::renderer::render::{RenderParams, Scene};
+use crate::renderer::samplers::sampler::Sampler;
+
+/// Shows a property of the surfaces seen by the camera, to debug geometry.
+pub enum DebugView {
+  /// Shading normals, facing the camera, mapped from [-1, 1] to [0, 1].
+  Normals,
+  /// Surface coordinates in the red and green channels, wrapped to [0, 1).
+  Uv,
+  /// Triangle vertex weights in the red, green and blue channels. Other shapes
+  /// are black.
+  Barycentric,
+}
+
+impl Integrator for DebugView {
+  fn radiance(
+    &self,
+    ray: &Ray,
+    scene: &Scene,
+    _params: &RenderParams,
+    _sampler: &mut dyn Sampler,
+  ) -> Color {
+    let Some(hit) = scene.world.hit(ray, lighting::hittable_range()) else {
+      return Color::default();
+    };
+
+    match self {
+      DebugView::Normals => 0.5 * (hit.normal() + Vec3::from(1.0)),
+      DebugView::Uv => {
+        let uv = hit.uv();
+        Color::new(uv.x().rem_euclid(1.0), uv.y().rem_euclid(1.0), 0.0)
+      }
+      DebugView::Barycentric => match hit.barycentric() {
+        Some(barycentric) => {
+          let (u, v) = (barycentric.x(), barycentric.y());
+          Color::new(1.0 - u - v, u, v)
+        }
+        None => Color::default(),
+      },
+    }
+  }
+}
@@ -0,0 +1,27 @@
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec3::Color;
+use crate::renderer::integrators::integrator::Integrator;
+use crate::renderer::integrators::lighting;
+use crate::renderer::render::{RenderParams, Scene};
+use crate::renderer::samplers::sampler::Sampler;
+
+/// Lights the surfaces seen by the camera with direct light only, so mirrors
+/// and glass appear black.
+pub struct DirectLighting;
+
+impl Integrator for DirectLighting {
+  fn radiance(
+    &self,
+    ray: &Ray,
+    scene: &Scene,
+    params: &RenderParams,
+    sampler: &mut dyn Sampler,
+  ) -> Color {
+    match scene.world.hit(ray, lighting::hittable_range()) {
+      Some(hit) => {
+        hit.material().emitted(&hit) + lighting::direct_lighting(ray, &hit, scene, params, sampler)
+      }
+      None => lighting::escaped(ray, scene, params, None),
+    }
+  }
+}
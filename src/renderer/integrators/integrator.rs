@@ -0,0 +1,58 @@
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec3::Color;
+use crate::renderer::integrators::ambient_occlusion::AmbientOcclusion;
+use crate::renderer::integrators::debug::DebugView;
+use crate::renderer::integrators::direct::DirectLighting;
+use crate::renderer::integrators::path::PathTracer;
+use crate::renderer::integrators::whitted::Whitted;
+use crate::renderer::render::{RenderParams, Scene};
+use crate::renderer::samplers::sampler::Sampler;
+use clap::ValueEnum;
+use serde::Deserialize;
+
+/// An algorithm for estimating the light arriving along camera rays.
+pub trait Integrator: Send + Sync {
+  /// Estimates the radiance arriving at the origin of `ray` along it.
+  fn radiance(
+    &self,
+    ray: &Ray,
+    scene: &Scene,
+    params: &RenderParams,
+    sampler: &mut dyn Sampler,
+  ) -> Color;
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum IntegratorKind {
+  /// Unbiased path tracing with light sampling.
+  #[default]
+  Path,
+  /// Direct lighting, plus perfect reflection and refraction.
+  Whitted,
+  /// The fraction of the hemisphere above each surface which is unoccluded.
+  AmbientOcclusion,
+  /// Light reaching surfaces straight from light sources only.
+  Direct,
+  /// Shading normals as colours.
+  Normals,
+  /// Surface coordinates as colours.
+  Uv,
+  /// Triangle barycentric coordinates as colours.
+  Barycentric,
+}
+
+impl IntegratorKind {
+  pub fn integrator(self) -> &'static dyn Integrator {
+    match self {
+      IntegratorKind::Path => &PathTracer,
+      IntegratorKind::Whitted => &Whitted,
+      IntegratorKind::AmbientOcclusion => &AmbientOcclusion,
+      IntegratorKind::Direct => &DirectLighting,
+      IntegratorKind::Normals => &DebugView::Normals,
+      IntegratorKind::Uv => &DebugView::Uv,
+      IntegratorKind::Barycentric => &DebugView::Barycentric,
+    }
+  }
+}
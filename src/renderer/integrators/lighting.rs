@@ -0,0 +1,151 @@
+use crate::renderer::core::math::Range;
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec3::Color;
+use crate::renderer::render::{RenderParams, Scene};
+use crate::renderer::samplers::sampler::Sampler;
+use crate::renderer::scene::hittable::Hit;
+
+/// The range of times along a ray in which it can hit something, skipping the
+/// surface it starts on.
+pub fn hittable_range() -> Range<f64> {
+  Range::new(0.001, f64::INFINITY)
+}
+
+/// Estimates the light reaching a diffuse `hit` directly from the environment,
+/// by casting a shadow ray in a direction sampled from the environment.
+pub fn sample_environment(
+  ray: &Ray,
+  hit: &Hit,
+  scene: &Scene,
+  params: &RenderParams,
+  sampler: &mut dyn Sampler,
+) -> Color {
+  let Some((direction, light_pdf)) = scene.environment.sample(sampler) else {
+    return Color::default();
+  };
+
+  let material = hit.material();
+  let reflectance = material.evaluate(ray, hit, &direction);
+  if reflectance.near_zero() {
+    return Color::default();
+  }
+
+  let shadow_ray = Ray::new(hit.point(), direction);
+  if scene.world.hit(&shadow_ray, hittable_range()).is_some() {
+    return Color::default();
+  }
+
+  let weight = params
+    .mis_heuristic
+    .weight(light_pdf, material.scattering_pdf(ray, hit, &direction));
+  reflectance * scene.environment.radiance(&direction) * (weight / light_pdf)
+}
+
+/// Estimates the light reaching a diffuse `hit` directly from the scene's
+/// lights, by casting a shadow ray towards a point on a random light.
+pub fn sample_lights(
+  ray: &Ray,
+  hit: &Hit,
+  scene: &Scene,
+  params: &RenderParams,
+  sampler: &mut dyn Sampler,
+) -> Color {
+  if scene.lights.is_empty() {
+    return Color::default();
+  }
+
+  let index = (sampler.get_1d() * scene.lights.len() as f64) as usize;
+  let light = &scene.lights[index.min(scene.lights.len() - 1)];
+  let Some((point, _)) = light.sample_point(&hit.point(), sampler) else {
+    return Color::default();
+  };
+
+  let direction = point - hit.point();
+  let material = hit.material();
+  let reflectance = material.evaluate(ray, hit, &direction);
+  if reflectance.near_zero() {
+    return Color::default();
+  }
+
+  // The light seen along `direction` counts even if it is not the sampled one,
+  // since the density covers every light which could have been picked.
+  let shadow_ray = Ray::new(hit.point(), direction);
+  let Some(light_hit) = scene.world.hit(&shadow_ray, hittable_range()) else {
+    return Color::default();
+  };
+
+  let light_pdf = scene.light_pdf(&hit.point(), &direction);
+  if light_pdf <= 0.0 {
+    return Color::default();
+  }
+
+  let weight = params
+    .mis_heuristic
+    .weight(light_pdf, material.scattering_pdf(ray, hit, &direction));
+  reflectance * light_hit.material().emitted(&light_hit) * (weight / light_pdf)
+}
+
+/// The light emitted at `hit`, reached by `ray`. If `ray` was scattered from a
+/// diffuse surface with density `scatter_pdf`, light sampling could also have
+/// found the emitter, so the emission is weighted against it.
+pub fn emitted(
+  ray: &Ray,
+  hit: &Hit,
+  scene: &Scene,
+  params: &RenderParams,
+  scatter_pdf: Option<f64>,
+) -> Color {
+  let emitted = hit.material().emitted(hit);
+
+  match scatter_pdf {
+    Some(pdf) if !emitted.near_zero() => {
+      let light_pdf = scene.light_pdf(&ray.origin(), &ray.direction());
+      emitted * params.mis_heuristic.weight(pdf, light_pdf)
+    }
+    _ => emitted,
+  }
+}
+
+/// The environment seen by `ray` as it escapes the scene, weighted like
+/// `emitted`.
+pub fn escaped(ray: &Ray, scene: &Scene, params: &RenderParams, scatter_pdf: Option<f64>) -> Color {
+  let direction = ray.direction();
+  let radiance = scene.environment.radiance(&direction);
+
+  match scatter_pdf {
+    Some(pdf) => {
+      radiance
+        * params
+          .mis_heuristic
+          .weight(pdf, scene.environment.pdf(&direction))
+    }
+    None => radiance,
+  }
+}
+
+/// Estimates the light reaching `hit` straight from lights and the
+/// environment, by combining light sampling with a single scattered ray.
+/// Only diffuse surfaces receive direct light.
+pub fn direct_lighting(
+  ray: &Ray,
+  hit: &Hit,
+  scene: &Scene,
+  params: &RenderParams,
+  sampler: &mut dyn Sampler,
+) -> Color {
+  let Some(scatter) = hit.material().scatter(ray, hit, sampler) else {
+    return Color::default();
+  };
+  let Some(scatter_pdf) = scatter.pdf() else {
+    return Color::default();
+  };
+
+  let scattered = match scene.world.hit(scatter.ray(), hittable_range()) {
+    Some(next) => emitted(scatter.ray(), &next, scene, params, Some(scatter_pdf)),
+    None => escaped(scatter.ray(), scene, params, Some(scatter_pdf)),
+  };
+
+  sample_environment(ray, hit, scene, params, sampler)
+    + sample_lights(ray, hit, scene, params, sampler)
+    + (scatter.attenuation() * scattered)
+}
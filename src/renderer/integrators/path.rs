@@ -0,0 +1,67 @@
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec3::Color;
+use crate::renderer::integrators::integrator::Integrator;
+use crate::renderer::integrators::lighting;
+use crate::renderer::render::{RenderParams, Scene};
+use crate::renderer::samplers::sampler::Sampler;
+
+/// Follows each ray as it bounces around the scene, sampling lights directly
+/// at every diffuse surface along the way. After `roulette_depth` bounces,
+/// paths are randomly ended with a chance that grows as their throughput falls.
+pub struct PathTracer;
+
+impl Integrator for PathTracer {
+  fn radiance(
+    &self,
+    ray: &Ray,
+    scene: &Scene,
+    params: &RenderParams,
+    sampler: &mut dyn Sampler,
+  ) -> Color {
+    let mut radiance = Color::default();
+    let mut throughput = Color::from(1.0);
+    let mut ray = *ray;
+    // The density with which `ray` was scattered, if light sampling could also
+    // have found where it goes.
+    let mut scatter_pdf = None;
+
+    for bounce in 0..params.max_bounces {
+      let Some(hit) = scene.world.hit(&ray, lighting::hittable_range()) else {
+        radiance += throughput * lighting::escaped(&ray, scene, params, scatter_pdf);
+        break;
+      };
+
+      radiance += throughput * lighting::emitted(&ray, &hit, scene, params, scatter_pdf);
+
+      let Some(scatter) = hit.material().scatter(&ray, &hit, sampler) else {
+        break;
+      };
+
+      // Only diffuse scatters can be combined with light sampling.
+      if scatter.pdf().is_some() {
+        radiance += throughput
+          * (lighting::sample_environment(&ray, &hit, scene, params, sampler)
+            + lighting::sample_lights(&ray, &hit, scene, params, sampler));
+      }
+
+      throughput *= scatter.attenuation();
+
+      if bounce + 1 >= params.roulette_depth {
+        // Dividing survivors by their chance of surviving keeps the estimate
+        // unbiased.
+        let (red, green, blue) = throughput.tuple();
+        let survival = red.max(green).max(blue).min(1.0);
+
+        if sampler.get_1d() >= survival {
+          break;
+        }
+        throughput = throughput / survival;
+      }
+
+      ray = *scatter.ray();
+      scatter_pdf = scatter.pdf();
+    }
+
+    radiance
+  }
+}
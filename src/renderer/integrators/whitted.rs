@@ -0,0 +1,52 @@
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec3::Color;
+use crate::renderer::integrators::integrator::Integrator;
+use crate::renderer::integrators::lighting;
+use crate::renderer::render::{RenderParams, Scene};
+use crate::renderer::samplers::sampler::Sampler;
+
+/// Follows rays through mirrors and glass, and lights the diffuse surfaces they
+/// reach with direct light only.
+pub struct Whitted;
+
+impl Whitted {
+  fn trace(
+    ray: &Ray,
+    scene: &Scene,
+    params: &RenderParams,
+    bounce_depth: usize,
+    sampler: &mut dyn Sampler,
+  ) -> Color {
+    if bounce_depth == 0 {
+      return Color::default();
+    }
+
+    let Some(hit) = scene.world.hit(ray, lighting::hittable_range()) else {
+      return lighting::escaped(ray, scene, params, None);
+    };
+
+    let emitted = hit.material().emitted(&hit);
+
+    match hit.material().scatter(ray, &hit, sampler) {
+      Some(scatter) if scatter.pdf().is_none() => {
+        emitted
+          + (scatter.attenuation()
+            * Self::trace(scatter.ray(), scene, params, bounce_depth - 1, sampler))
+      }
+      Some(_) => emitted + lighting::direct_lighting(ray, &hit, scene, params, sampler),
+      None => emitted,
+    }
+  }
+}
+
+impl Integrator for Whitted {
+  fn radiance(
+    &self,
+    ray: &Ray,
+    scene: &Scene,
+    params: &RenderParams,
+    sampler: &mut dyn Sampler,
+  ) -> Color {
+    Self::trace(ray, scene, params, params.max_bounces, sampler)
+  }
+}
@@ -0,0 +1,174 @@
+use crate::renderer::core::vec3::Color;
+use crate::renderer::loaders::statement::{self, Statement};
+use crate::renderer::materials::dielectric::Dielectric;
+use crate::renderer::materials::diffuse_light::DiffuseLight;
+use crate::renderer::materials::lambertian::Lambertian;
+use crate::renderer::materials::material::Material;
+use crate::renderer::materials::metal::Metal;
+use crate::types::parse_error::ParseError;
+use crate::types::result::Result;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+pub type Materials = HashMap<String, Arc<dyn Material>>;
+
+/// The subset of an MTL material definition which maps onto our materials.
+struct MaterialDefinition {
+  name: String,
+  diffuse: Color,
+  specular: Color,
+  emissive: Color,
+  specular_exponent: f64,
+  refractive_index: f64,
+  dissolve: f64,
+  illumination: usize,
+}
+
+impl MaterialDefinition {
+  fn new(name: String) -> Self {
+    Self {
+      name,
+      diffuse: Color::from(0.8),
+      specular: Color::default(),
+      emissive: Color::default(),
+      specular_exponent: 0.0,
+      refractive_index: 1.5,
+      dissolve: 1.0,
+      illumination: 1,
+    }
+  }
+
+  /// Picks the closest of our materials. Emissive materials become lights,
+  /// transparent materials become dielectrics, materials with ray traced
+  /// reflections become metals, and everything else is diffuse.
+  fn build(&self) -> Arc<dyn Material> {
+    let transparent = (self.dissolve < 1.0) || matches!(self.illumination, 4 | 6 | 7 | 9);
+    let reflective = matches!(self.illumination, 3 | 5 | 8);
+
+    if !self.emissive.near_zero() {
+      Arc::new(DiffuseLight::new(self.emissive))
+    } else if transparent {
+      Arc::new(Dielectric::new(self.refractive_index))
+    } else if reflective {
+      // Map the Phong exponent onto fuzziness, so that a sharp highlight makes a
+      // mirror and a broad highlight makes a rough metal.
+      let fuzziness = (2.0 / (self.specular_exponent + 2.0)).sqrt();
+      let albedo = if self.specular.near_zero() {
+        self.diffuse
+      } else {
+        self.specular
+      };
+
+      Arc::new(Metal::new(albedo, fuzziness))
+    } else {
+      Arc::new(Lambertian::new(self.diffuse))
+    }
+  }
+}
+
+fn color(statement: &Statement) -> std::result::Result<Color, ParseError> {
+  let components = statement.floats(1, 3)?;
+
+  if components.iter().any(|component| *component < 0.0) {
+    return Err(statement.error(format!(
+      "`{}` color components must not be negative.",
+      statement.keyword()
+    )));
+  }
+
+  // A single component is a grey.
+  Ok(match components[..] {
+    [grey] => Color::from(grey),
+    [red, green, blue] => Color::new(red, green, blue),
+    _ => {
+      return Err(statement.error(format!(
+        "`{}` expects 1 or 3 color components.",
+        statement.keyword()
+      )))
+    }
+  })
+}
+
+fn fraction(statement: &Statement) -> std::result::Result<f64, ParseError> {
+  let value = statement.float()?;
+
+  if (0.0..=1.0).contains(&value) {
+    Ok(value)
+  } else {
+    Err(statement.error(format!(
+      "`{}` must be between 0 and 1.",
+      statement.keyword()
+    )))
+  }
+}
+
+pub fn parse(file: &str, source: &str) -> std::result::Result<Materials, ParseError> {
+  let mut definitions: Vec<MaterialDefinition> = Vec::new();
+
+  for statement in statement::statements(file, source) {
+    if statement.keyword() == "newmtl" {
+      definitions.push(MaterialDefinition::new(statement.name()?));
+      continue;
+    }
+
+    let definition = match definitions.last_mut() {
+      Some(definition) => definition,
+      None => {
+        return Err(statement.error(format!(
+          "`{}` appears before any `newmtl`.",
+          statement.keyword()
+        )))
+      }
+    };
+
+    match statement.keyword() {
+      "Kd" => definition.diffuse = color(&statement)?,
+      "Ks" => definition.specular = color(&statement)?,
+      "Ke" => definition.emissive = color(&statement)?,
+      "Ns" => {
+        definition.specular_exponent = statement.float()?;
+
+        if definition.specular_exponent < 0.0 {
+          return Err(statement.error("`Ns` must not be negative."));
+        }
+      }
+      "Ni" => {
+        definition.refractive_index = statement.float()?;
+
+        if definition.refractive_index <= 0.0 {
+          return Err(statement.error("`Ni` refractive index must be positive."));
+        }
+      }
+      "d" => definition.dissolve = fraction(&statement)?,
+      "Tr" => definition.dissolve = 1.0 - fraction(&statement)?,
+      "illum" => {
+        definition.illumination = statement.integer()?;
+
+        if definition.illumination > 10 {
+          return Err(statement.error("`illum` must be between 0 and 10."));
+        }
+      }
+      // Texture maps and other properties have no equivalent in our materials.
+      _ => {}
+    }
+  }
+
+  Ok(
+    definitions
+      .into_iter()
+      .map(|definition| {
+        let material = definition.build();
+        (definition.name, material)
+      })
+      .collect(),
+  )
+}
+
+pub fn load(path: &Path) -> Result<Materials> {
+  let source = fs::read_to_string(path)
+    .map_err(|err| format!("Could not read material library {}: {err}", path.display()))?;
+
+  Ok(parse(&path.display().to_string(), &source)?)
+}
@@ -0,0 +1,230 @@
+use crate::renderer::core::vec2::Vec2;
+use crate::renderer::core::vec3::{Point3, Vec3};
+use crate::renderer::loaders::mtl::{self, Materials};
+use crate::renderer::loaders::statement::{self, Statement};
+use crate::renderer::materials::material::Material;
+use crate::renderer::scene::mesh::{self, MeshBuffers, MeshFace, TriangleMesh};
+use crate::types::parse_error::ParseError;
+use crate::types::result::Result;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The indices of a face corner into the position, UV and normal buffers.
+#[derive(Clone, Copy)]
+struct Corner {
+  position: usize,
+  uv: Option<usize>,
+  normal: Option<usize>,
+}
+
+/// Faces which share a group and material, and so become a single mesh.
+struct FaceGroup {
+  material: Arc<dyn Material>,
+  faces: Vec<MeshFace>,
+}
+
+/// Resolves a 1-based or negative (relative to the end) OBJ index into an index
+/// into a buffer of `count` elements.
+fn resolve_index(
+  statement: &Statement,
+  index: &str,
+  count: usize,
+  kind: &str,
+) -> std::result::Result<usize, ParseError> {
+  let value = index
+    .parse::<isize>()
+    .map_err(|_| statement.error(format!("Invalid {kind} index `{index}`.")))?;
+
+  let resolved = match value {
+    0 => None,
+    value if value > 0 => Some(value as usize - 1),
+    value => count.checked_sub(value.unsigned_abs()),
+  };
+
+  resolved
+    .filter(|&resolved| resolved < count)
+    .ok_or_else(|| {
+      statement.error(format!(
+        "Index {value} does not refer to a {kind}; {count} are defined so far."
+      ))
+    })
+}
+
+fn parse_corner(
+  statement: &Statement,
+  corner: &str,
+  buffers: &MeshBuffers,
+) -> std::result::Result<Corner, ParseError> {
+  let mut indices = corner.split('/');
+  let optional_index = |index: Option<&str>, count, kind| {
+    index
+      .filter(|index| !index.is_empty())
+      .map(|index| resolve_index(statement, index, count, kind))
+      .transpose()
+  };
+
+  let position = resolve_index(
+    statement,
+    indices.next().unwrap_or_default(),
+    buffers.positions.len(),
+    "vertex",
+  )?;
+  let uv = optional_index(indices.next(), buffers.uvs.len(), "texture coordinate")?;
+  let normal = optional_index(indices.next(), buffers.normals.len(), "normal")?;
+
+  if indices.next().is_some() {
+    return Err(statement.error(format!("Invalid face corner `{corner}`.")));
+  }
+
+  Ok(Corner {
+    position,
+    uv,
+    normal,
+  })
+}
+
+/// Triangulates a polygon as a fan around its first corner.
+fn triangulate(corners: &[Corner]) -> impl Iterator<Item = MeshFace> + '_ {
+  corners.windows(2).skip(1).map(|pair| {
+    let triangle = [corners[0], pair[0], pair[1]];
+    let attribute = |get: fn(&Corner) -> Option<usize>| {
+      triangle
+        .iter()
+        .map(get)
+        .collect::<Option<Vec<_>>>()
+        .map(|indices| [indices[0], indices[1], indices[2]])
+    };
+
+    MeshFace {
+      positions: triangle.map(|corner| corner.position),
+      normals: attribute(|corner| corner.normal),
+      uvs: attribute(|corner| corner.uv),
+    }
+  })
+}
+
+/// Parses OBJ `source` into one mesh per group and material, all sharing the
+/// same vertex buffers. Faces with no material use `default_material`, and
+/// `load_library` is called to load the materials of each `mtllib`.
+pub fn parse(
+  file: &str,
+  source: &str,
+  default_material: Arc<dyn Material>,
+  mut load_library: impl FnMut(&str) -> Result<Materials>,
+) -> Result<Vec<TriangleMesh>> {
+  let mut buffers = MeshBuffers::default();
+  let mut materials = Materials::new();
+
+  let mut groups: Vec<FaceGroup> = Vec::new();
+  let mut group_indices: HashMap<(String, String), usize> = HashMap::new();
+  let mut group_name = String::new();
+  let mut material_name = String::new();
+  let mut material = Arc::clone(&default_material);
+
+  for statement in statement::statements(file, source) {
+    match statement.keyword() {
+      "v" => {
+        let components = statement.floats(3, 4)?;
+        buffers
+          .positions
+          .push(Point3::new(components[0], components[1], components[2]));
+      }
+      "vn" => {
+        let components = statement.floats(3, 3)?;
+        let normal = Vec3::new(components[0], components[1], components[2]);
+
+        if normal.near_zero() {
+          return Err(statement.error("Normals must not be zero.").into());
+        }
+
+        buffers.normals.push(normal);
+      }
+      "vt" => {
+        let components = statement.floats(1, 3)?;
+        buffers.uvs.push(Vec2::new(
+          components[0],
+          components.get(1).copied().unwrap_or_default(),
+        ));
+      }
+      "f" => {
+        if statement.arguments().len() < 3 {
+          return Err(statement.error("Faces need at least 3 vertices.").into());
+        }
+
+        let corners = statement
+          .arguments()
+          .iter()
+          .map(|corner| parse_corner(&statement, corner, &buffers))
+          .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let key = (group_name.clone(), material_name.clone());
+        let index = *group_indices.entry(key).or_insert_with(|| {
+          groups.push(FaceGroup {
+            material: Arc::clone(&material),
+            faces: Vec::new(),
+          });
+          groups.len() - 1
+        });
+
+        groups[index].faces.extend(triangulate(&corners));
+      }
+      "g" | "o" => {
+        group_name = statement.arguments().join(" ");
+      }
+      "usemtl" => {
+        material_name = statement.name()?;
+        material =
+          Arc::clone(materials.get(&material_name).ok_or_else(|| {
+            statement.error(format!("Material `{material_name}` is not defined."))
+          })?);
+      }
+      "mtllib" => {
+        for library in statement.arguments() {
+          materials.extend(load_library(library)?);
+        }
+      }
+      // Smoothing groups, lines, points and free-form geometry are not supported.
+      _ => {}
+    }
+  }
+
+  let buffers = Arc::new(buffers);
+
+  Ok(
+    groups
+      .into_iter()
+      .map(|group| {
+        // Each mesh is crossed on its own, so it must be closed by itself.
+        let solid = mesh::is_closed(&group.faces);
+        TriangleMesh::new(Arc::clone(&buffers), group.faces, group.material).with_solid(solid)
+      })
+      .collect(),
+  )
+}
+
+/// Loads the OBJ file at `path`, along with the material libraries it refers
+/// to, which are looked up relative to the OBJ file. The paths of the files
+/// read are added to `inputs`.
+pub fn load(
+  path: &Path,
+  default_material: Arc<dyn Material>,
+  inputs: &mut Vec<PathBuf>,
+) -> Result<Vec<TriangleMesh>> {
+  let source = fs::read_to_string(path)
+    .map_err(|err| format!("Could not read model {}: {err}", path.display()))?;
+  let directory = path.parent().unwrap_or(Path::new(""));
+  inputs.push(path.to_owned());
+
+  parse(
+    &path.display().to_string(),
+    &source,
+    default_material,
+    |library| {
+      let library = directory.join(library);
+      inputs.push(library.clone());
+      mtl::load(&library)
+    },
+  )
+}
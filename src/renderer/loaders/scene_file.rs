@@ -0,0 +1,952 @@
+use crate::image::format::ImageFormat;
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::transform::Transform;
+use crate::renderer::core::vec2::Vec2;
+use crate::renderer::core::vec3::{Color, Vec3};
+use crate::renderer::integrators::integrator::IntegratorKind;
+use crate::renderer::loaders::obj;
+use crate::renderer::materials::dielectric::Dielectric;
+use crate::renderer::materials::diffuse_light::DiffuseLight;
+use crate::renderer::materials::lambertian::Lambertian;
+use crate::renderer::materials::material::Material;
+use crate::renderer::materials::metal::Metal;
+use crate::renderer::render::{MisHeuristic, RenderParams, Scene};
+use crate::renderer::samplers::sampler::SamplerKind;
+use crate::renderer::scene::bvh::Bvh;
+use crate::renderer::scene::camera::Camera;
+use crate::renderer::scene::csg::{Csg, CsgOperation};
+use crate::renderer::scene::cuboid::Cuboid;
+use crate::renderer::scene::disk::Disk;
+use crate::renderer::scene::environment::{Environment, EnvironmentMap};
+use crate::renderer::scene::hittable::Hittable;
+use crate::renderer::scene::instance::Instance;
+use crate::renderer::scene::plane::Plane;
+use crate::renderer::scene::quad::Quad;
+use crate::renderer::scene::quadric::{Quadric, QuadricCoefficients};
+use crate::renderer::scene::sphere::Sphere;
+use crate::renderer::scene::torus::Torus;
+use crate::renderer::scene::triangle::Triangle;
+use crate::types::parse_error::ParseError;
+use crate::types::result::Result;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use toml::Spanned;
+
+/// A number which must be greater than zero.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(try_from = "f64")]
+struct Positive(f64);
+
+impl TryFrom<f64> for Positive {
+  type Error = String;
+
+  fn try_from(value: f64) -> std::result::Result<Self, Self::Error> {
+    if value > 0.0 {
+      Ok(Self(value))
+    } else {
+      Err(format!("expected a positive number, found {value}"))
+    }
+  }
+}
+
+/// A number which must not be negative.
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(try_from = "f64")]
+struct NonNegative(f64);
+
+impl TryFrom<f64> for NonNegative {
+  type Error = String;
+
+  fn try_from(value: f64) -> std::result::Result<Self, Self::Error> {
+    if value >= 0.0 {
+      Ok(Self(value))
+    } else {
+      Err(format!("expected a non-negative number, found {value}"))
+    }
+  }
+}
+
+/// A number within [0, 1].
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(try_from = "f64")]
+struct Fraction(f64);
+
+impl TryFrom<f64> for Fraction {
+  type Error = String;
+
+  fn try_from(value: f64) -> std::result::Result<Self, Self::Error> {
+    if (0.0..=1.0).contains(&value) {
+      Ok(Self(value))
+    } else {
+      Err(format!("expected a number between 0 and 1, found {value}"))
+    }
+  }
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(try_from = "[f64; 3]")]
+struct ColorDescription(Color);
+
+impl TryFrom<[f64; 3]> for ColorDescription {
+  type Error = String;
+
+  fn try_from([red, green, blue]: [f64; 3]) -> std::result::Result<Self, Self::Error> {
+    if [red, green, blue].iter().all(|component| *component >= 0.0) {
+      Ok(Self(Color::new(red, green, blue)))
+    } else {
+      Err("color components must not be negative".to_owned())
+    }
+  }
+}
+
+fn vector([x, y, z]: [f64; 3]) -> Vec3 {
+  Vec3::new(x, y, z)
+}
+
+fn default_max_bounces() -> usize {
+  50
+}
+
+fn default_min_samples_per_pixel() -> usize {
+  16
+}
+
+fn default_roulette_depth() -> usize {
+  3
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RenderDescription {
+  width: usize,
+  height: usize,
+  samples_per_pixel: usize,
+  adaptive_threshold: Option<Positive>,
+  #[serde(default = "default_min_samples_per_pixel")]
+  min_samples_per_pixel: usize,
+  #[serde(default = "default_max_bounces")]
+  max_bounces: usize,
+  #[serde(default = "default_roulette_depth")]
+  roulette_depth: usize,
+  /// Replaces the environment with `Environment::Debug`.
+  #[serde(default)]
+  debug: bool,
+  #[serde(default)]
+  sampler: SamplerKind,
+  #[serde(default)]
+  integrator: IntegratorKind,
+  #[serde(default)]
+  mis_heuristic: MisHeuristic,
+  #[serde(default = "default_occlusion_distance")]
+  occlusion_distance: Positive,
+}
+
+fn default_occlusion_distance() -> Positive {
+  Positive(f64::INFINITY)
+}
+
+fn default_view_up() -> [f64; 3] {
+  [0.0, 1.0, 0.0]
+}
+
+fn default_focus_distance() -> Positive {
+  Positive(1.0)
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CameraDescription {
+  look_from: [f64; 3],
+  look_at: [f64; 3],
+  #[serde(default = "default_view_up")]
+  view_up: [f64; 3],
+  vertical_fov: Positive,
+  #[serde(default)]
+  aperture_radius: NonNegative,
+  #[serde(default = "default_focus_distance")]
+  focus_distance: Positive,
+}
+
+impl CameraDescription {
+  fn build(&self, aspect_ratio: f64) -> Result<Camera> {
+    let look_from = vector(self.look_from);
+    let look_at = vector(self.look_at);
+    let view_up = vector(self.view_up);
+    let view = look_at - look_from;
+
+    if view.length() == 0.0 {
+      return Err("The camera's `look_from` and `look_at` must be different points.".into());
+    }
+    // The image's sideways direction is perpendicular to both.
+    if view.cross(&view_up).length() <= 1e-8 * view.length() * view_up.length() {
+      return Err("The camera's `view_up` must not be parallel to the view direction.".into());
+    }
+    if self.vertical_fov.0 >= 180.0 {
+      return Err("The camera's `vertical_fov` must be less than 180 degrees.".into());
+    }
+
+    Ok(Camera::new(
+      look_from,
+      look_at,
+      view_up,
+      self.vertical_fov.0,
+      aspect_ratio,
+      self.aperture_radius.0,
+      self.focus_distance.0,
+    ))
+  }
+}
+
+fn default_intensity() -> NonNegative {
+  NonNegative(1.0)
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+enum EnvironmentDescription {
+  Constant {
+    color: ColorDescription,
+  },
+  Gradient {
+    top: ColorDescription,
+    bottom: ColorDescription,
+  },
+  /// An equirectangular .hdr or .pfm image, turned `rotation` degrees about the
+  /// vertical axis.
+  Map {
+    path: PathBuf,
+    #[serde(default)]
+    rotation: f64,
+    #[serde(default = "default_intensity")]
+    intensity: NonNegative,
+  },
+}
+
+impl EnvironmentDescription {
+  /// Builds the environment, adding the paths of any files read to `inputs`.
+  fn build(&self, directory: &Path, inputs: &mut Vec<PathBuf>) -> Result<Environment> {
+    Ok(match self {
+      EnvironmentDescription::Constant { color } => Environment::Constant(color.0),
+      EnvironmentDescription::Gradient { top, bottom } => Environment::Gradient {
+        top: top.0,
+        bottom: bottom.0,
+      },
+      EnvironmentDescription::Map {
+        path,
+        rotation,
+        intensity,
+      } => {
+        let path = directory.join(path);
+        let image = ImageFormat::read(&path)?;
+        inputs.push(path);
+
+        Environment::Map(EnvironmentMap::new(image, *rotation, intensity.0))
+      }
+    })
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+enum MaterialDescription {
+  Lambertian {
+    albedo: ColorDescription,
+  },
+  Metal {
+    albedo: ColorDescription,
+    #[serde(default)]
+    fuzziness: Fraction,
+  },
+  Dielectric {
+    refractive_index: Positive,
+  },
+  DiffuseLight {
+    emit: ColorDescription,
+  },
+}
+
+impl MaterialDescription {
+  fn build(&self) -> Arc<dyn Material> {
+    match *self {
+      MaterialDescription::Lambertian { albedo } => Arc::new(Lambertian::new(albedo.0)),
+      MaterialDescription::Metal { albedo, fuzziness } => {
+        Arc::new(Metal::new(albedo.0, fuzziness.0))
+      }
+      MaterialDescription::Dielectric { refractive_index } => {
+        Arc::new(Dielectric::new(refractive_index.0))
+      }
+      MaterialDescription::DiffuseLight { emit } => Arc::new(DiffuseLight::new(emit.0)),
+    }
+  }
+}
+
+/// Non-zero scale factors along each axis.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(try_from = "[f64; 3]")]
+struct ScaleDescription(Vec3);
+
+impl TryFrom<[f64; 3]> for ScaleDescription {
+  type Error = String;
+
+  fn try_from(factors: [f64; 3]) -> std::result::Result<Self, Self::Error> {
+    if factors.iter().all(|factor| *factor != 0.0) {
+      Ok(Self(vector(factors)))
+    } else {
+      Err("scale factors must not be zero".to_owned())
+    }
+  }
+}
+
+fn default_tile_size() -> Positive {
+  Positive(1.0)
+}
+
+fn default_capped() -> bool {
+  true
+}
+
+fn default_scale() -> ScaleDescription {
+  ScaleDescription(Vec3::from(1.0))
+}
+
+/// Scales, then rotates around the X, Y and Z axes in turn by `rotate`
+/// degrees, then translates.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransformDescription {
+  #[serde(default = "default_scale")]
+  scale: ScaleDescription,
+  #[serde(default)]
+  rotate: [f64; 3],
+  #[serde(default)]
+  translate: [f64; 3],
+}
+
+impl TransformDescription {
+  fn build(&self) -> Transform {
+    let [x, y, z] = self.rotate;
+
+    Transform::scale(&self.scale.0)
+      .then(&Transform::rotate(&Vec3::new(1.0, 0.0, 0.0), x))
+      .then(&Transform::rotate(&Vec3::new(0.0, 1.0, 0.0), y))
+      .then(&Transform::rotate(&Vec3::new(0.0, 0.0, 1.0), z))
+      .then(&Transform::translate(&vector(self.translate)))
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+enum ObjectDescription {
+  Sphere {
+    center: [f64; 3],
+    radius: f64,
+    material: String,
+  },
+  Triangle {
+    vertices: [[f64; 3]; 3],
+    material: String,
+  },
+  /// A parallelogram spanning the edges `u` and `v` from `corner`, facing
+  /// along `u` × `v`.
+  Quad {
+    corner: [f64; 3],
+    u: [f64; 3],
+    v: [f64; 3],
+    material: String,
+  },
+  /// An infinite plane through `point`, with surface coordinates repeating
+  /// every `tile_size`.
+  Plane {
+    point: [f64; 3],
+    normal: [f64; 3],
+    #[serde(default = "default_tile_size")]
+    tile_size: Positive,
+    material: String,
+  },
+  Disk {
+    center: [f64; 3],
+    normal: [f64; 3],
+    radius: Positive,
+    material: String,
+  },
+  /// An axis-aligned box between the opposite corners `min` and `max`.
+  Cuboid {
+    min: [f64; 3],
+    max: [f64; 3],
+    material: String,
+  },
+  /// The following quadrics stand upright on the centre of their `base`, and
+  /// are closed off with flat caps unless `capped` is false.
+  Cylinder {
+    base: [f64; 3],
+    radius: Positive,
+    height: Positive,
+    #[serde(default = "default_capped")]
+    capped: bool,
+    material: String,
+  },
+  /// A cone narrowing to its tip, `height` above its base.
+  Cone {
+    base: [f64; 3],
+    radius: Positive,
+    height: Positive,
+    #[serde(default = "default_capped")]
+    capped: bool,
+    material: String,
+  },
+  /// A bowl `radius` wide at its rim, with its lowest point at `base`.
+  Paraboloid {
+    base: [f64; 3],
+    radius: Positive,
+    height: Positive,
+    #[serde(default = "default_capped")]
+    capped: bool,
+    material: String,
+  },
+  /// A hyperboloid of one sheet, `radius` wide at its ends and `waist_radius`
+  /// wide halfway up.
+  Hyperboloid {
+    base: [f64; 3],
+    radius: Positive,
+    waist_radius: NonNegative,
+    height: Positive,
+    #[serde(default = "default_capped")]
+    capped: bool,
+    material: String,
+  },
+  /// Any quadric surface, given by the `coefficients` of its equation, cut to
+  /// the box between `min` and `max`.
+  Quadric {
+    coefficients: QuadricCoefficients,
+    min: [f64; 3],
+    max: [f64; 3],
+    #[serde(default = "default_capped")]
+    capped: bool,
+    material: String,
+  },
+  /// A ring around the vertical axis through `center`, with a tube of
+  /// `minor_radius` thinner than the ring's `major_radius`.
+  Torus {
+    center: [f64; 3],
+    major_radius: Positive,
+    minor_radius: Positive,
+    material: String,
+  },
+  /// An OBJ model, where `material` is used for faces without an MTL material.
+  Mesh { path: PathBuf, material: String },
+  /// Copies of `object` placed by each of `transforms`, all sharing its
+  /// geometry.
+  Instances {
+    object: Box<ObjectDescription>,
+    transforms: Vec<TransformDescription>,
+  },
+  /// The solid made of the closed objects `left` and `right` by `operation`.
+  Csg {
+    operation: CsgOperation,
+    left: Box<ObjectDescription>,
+    right: Box<ObjectDescription>,
+  },
+}
+
+impl ObjectDescription {
+  /// The names of the materials the object uses.
+  fn materials(&self) -> Vec<&str> {
+    match self {
+      ObjectDescription::Sphere { material, .. }
+      | ObjectDescription::Triangle { material, .. }
+      | ObjectDescription::Quad { material, .. }
+      | ObjectDescription::Plane { material, .. }
+      | ObjectDescription::Disk { material, .. }
+      | ObjectDescription::Cuboid { material, .. }
+      | ObjectDescription::Cylinder { material, .. }
+      | ObjectDescription::Cone { material, .. }
+      | ObjectDescription::Paraboloid { material, .. }
+      | ObjectDescription::Hyperboloid { material, .. }
+      | ObjectDescription::Quadric { material, .. }
+      | ObjectDescription::Torus { material, .. }
+      | ObjectDescription::Mesh { material, .. } => vec![material],
+      ObjectDescription::Instances { object, .. } => object.materials(),
+      ObjectDescription::Csg { left, right, .. } => [left.materials(), right.materials()].concat(),
+    }
+  }
+
+  /// Builds the object into `objects`, also adding it to `lights` if it is
+  /// emissive, and the paths of any files read to `inputs`.
+  fn build(
+    &self,
+    directory: &Path,
+    materials: &HashMap<&str, Arc<dyn Material>>,
+    objects: &mut Vec<Arc<dyn Hittable>>,
+    lights: &mut Vec<Arc<dyn Hittable>>,
+    inputs: &mut Vec<PathBuf>,
+  ) -> Result<()> {
+    let mut add = |object: Arc<dyn Hittable>, emissive: bool| {
+      if emissive {
+        lights.push(Arc::clone(&object));
+      }
+      objects.push(object);
+    };
+
+    match self {
+      ObjectDescription::Sphere {
+        center,
+        radius,
+        material,
+      } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        // A negative radius turns the surface inside out, as for a bubble.
+        if *radius == 0.0 {
+          return Err("A sphere's `radius` must not be zero.".into());
+        }
+
+        let emissive = material.is_emissive();
+        add(
+          Arc::new(Sphere::new(vector(*center), *radius, material)),
+          emissive,
+        );
+      }
+      ObjectDescription::Triangle { vertices, material } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        let emissive = material.is_emissive();
+        add(
+          Arc::new(Triangle::new(vertices.map(vector), material)),
+          emissive,
+        );
+      }
+      ObjectDescription::Quad {
+        corner,
+        u,
+        v,
+        material,
+      } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        if vector(*u).cross(&vector(*v)).near_zero() {
+          return Err("A quad's edges `u` and `v` must not be parallel.".into());
+        }
+
+        let emissive = material.is_emissive();
+        add(
+          Arc::new(Quad::new(vector(*corner), vector(*u), vector(*v), material)),
+          emissive,
+        );
+      }
+      ObjectDescription::Plane {
+        point,
+        normal,
+        tile_size,
+        material,
+      } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        if vector(*normal).near_zero() {
+          return Err("A plane's `normal` must not be zero.".into());
+        }
+
+        let emissive = material.is_emissive();
+        add(
+          Arc::new(Plane::new(
+            vector(*point),
+            vector(*normal),
+            tile_size.0,
+            material,
+          )),
+          emissive,
+        );
+      }
+      ObjectDescription::Disk {
+        center,
+        normal,
+        radius,
+        material,
+      } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        if vector(*normal).near_zero() {
+          return Err("A disk's `normal` must not be zero.".into());
+        }
+
+        let emissive = material.is_emissive();
+        add(
+          Arc::new(Disk::new(
+            vector(*center),
+            vector(*normal),
+            radius.0,
+            material,
+          )),
+          emissive,
+        );
+      }
+      ObjectDescription::Cuboid { min, max, material } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        if !vector(*max)
+          .zip(&vector(*min), |max, min| max - min)
+          .all(|size| size > 0.0)
+        {
+          return Err("A cuboid's `max` must be above its `min` along every axis.".into());
+        }
+
+        let emissive = material.is_emissive();
+        add(
+          Arc::new(Cuboid::new(vector(*min), vector(*max), material)),
+          emissive,
+        );
+      }
+      ObjectDescription::Cylinder {
+        base,
+        radius,
+        height,
+        capped,
+        material,
+      } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        let emissive = material.is_emissive();
+        add(
+          Arc::new(Quadric::cylinder(
+            vector(*base),
+            radius.0,
+            height.0,
+            *capped,
+            material,
+          )),
+          emissive,
+        );
+      }
+      ObjectDescription::Cone {
+        base,
+        radius,
+        height,
+        capped,
+        material,
+      } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        let emissive = material.is_emissive();
+        add(
+          Arc::new(Quadric::cone(
+            vector(*base),
+            radius.0,
+            height.0,
+            *capped,
+            material,
+          )),
+          emissive,
+        );
+      }
+      ObjectDescription::Paraboloid {
+        base,
+        radius,
+        height,
+        capped,
+        material,
+      } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        let emissive = material.is_emissive();
+        add(
+          Arc::new(Quadric::paraboloid(
+            vector(*base),
+            radius.0,
+            height.0,
+            *capped,
+            material,
+          )),
+          emissive,
+        );
+      }
+      ObjectDescription::Hyperboloid {
+        base,
+        radius,
+        waist_radius,
+        height,
+        capped,
+        material,
+      } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        let emissive = material.is_emissive();
+        add(
+          Arc::new(Quadric::hyperboloid(
+            vector(*base),
+            radius.0,
+            waist_radius.0,
+            height.0,
+            *capped,
+            material,
+          )),
+          emissive,
+        );
+      }
+      ObjectDescription::Quadric {
+        coefficients,
+        min,
+        max,
+        capped,
+        material,
+      } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        if !vector(*max)
+          .zip(&vector(*min), |max, min| max - min)
+          .all(|size| size > 0.0)
+        {
+          return Err("A quadric's `max` must be above its `min` along every axis.".into());
+        }
+
+        let emissive = material.is_emissive();
+        add(
+          Arc::new(Quadric::new(
+            *coefficients,
+            Aabb::new(vector(*min), vector(*max)),
+            *capped,
+            material,
+          )),
+          emissive,
+        );
+      }
+      ObjectDescription::Torus {
+        center,
+        major_radius,
+        minor_radius,
+        material,
+      } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        // Otherwise the tube would pass through the axis and the surface
+        // through itself.
+        if minor_radius.0 >= major_radius.0 {
+          return Err("A torus's `minor_radius` must be less than its `major_radius`.".into());
+        }
+        let emissive = material.is_emissive();
+        add(
+          Arc::new(Torus::new(
+            vector(*center),
+            major_radius.0,
+            minor_radius.0,
+            material,
+          )),
+          emissive,
+        );
+      }
+      ObjectDescription::Mesh { path, material } => {
+        let material = Arc::clone(&materials[material.as_str()]);
+        for mesh in obj::load(&directory.join(path), material, inputs)? {
+          let emissive = mesh.material().is_emissive();
+          add(Arc::new(mesh), emissive);
+        }
+      }
+      ObjectDescription::Instances { object, transforms } => {
+        let (mut originals, mut original_lights) = (Vec::new(), Vec::new());
+        object.build(
+          directory,
+          materials,
+          &mut originals,
+          &mut original_lights,
+          inputs,
+        )?;
+
+        for original in &originals {
+          let emissive = original_lights
+            .iter()
+            .any(|light| Arc::ptr_eq(light, original));
+
+          for transform in transforms {
+            add(
+              Arc::new(Instance::new(Arc::clone(original), transform.build())),
+              emissive,
+            );
+          }
+        }
+      }
+      ObjectDescription::Csg {
+        operation,
+        left,
+        right,
+      } => {
+        add(
+          Arc::new(Csg::new(
+            *operation,
+            left.build_solid(directory, materials, inputs)?,
+            right.build_solid(directory, materials, inputs)?,
+          )),
+          false,
+        );
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Builds the object on its own, as a single `Hittable`.
+  fn build_solid(
+    &self,
+    directory: &Path,
+    materials: &HashMap<&str, Arc<dyn Material>>,
+    inputs: &mut Vec<PathBuf>,
+  ) -> Result<Arc<dyn Hittable>> {
+    // Light sampling would pick points on the parts of the surfaces which were
+    // cut away, so the lights are only found by rays hitting them.
+    let mut objects = Vec::new();
+    self.build(directory, materials, &mut objects, &mut Vec::new(), inputs)?;
+
+    let object: Arc<dyn Hittable> = if objects.len() == 1 {
+      objects.remove(0)
+    } else {
+      Arc::new(Bvh::new(objects))
+    };
+
+    // `Csg` pairs up the crossings of each part into spans inside it.
+    if !object.is_solid() {
+      return Err(
+        "The `left` and `right` of a `csg` object must be closed solids, such as spheres, \
+         cuboids, tori, capped quadrics or closed meshes."
+          .into(),
+      );
+    }
+
+    Ok(object)
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SceneDescription {
+  render: RenderDescription,
+  camera: Spanned<CameraDescription>,
+  /// Defaults to a pale blue sky.
+  environment: Option<EnvironmentDescription>,
+  #[serde(default)]
+  materials: HashMap<String, MaterialDescription>,
+  #[serde(default)]
+  objects: Vec<Spanned<ObjectDescription>>,
+}
+
+fn line_of(source: &str, offset: usize) -> usize {
+  source[..offset].matches('\n').count() + 1
+}
+
+/// Parses a TOML scene description. Relative paths inside it, such as those of
+/// meshes, are resolved against `directory`. The render settings from the
+/// scene are passed through `configure` before the scene is built, so that
+/// they can be overridden.
+pub fn parse(
+  file: &str,
+  source: &str,
+  directory: &Path,
+  configure: impl FnOnce(&mut RenderParams) -> Result<()>,
+) -> Result<(RenderParams, Scene)> {
+  let description: SceneDescription =
+    toml::from_str(source).map_err(|err| format!("{file}: {err}"))?;
+
+  let render = &description.render;
+  if (render.width < 2) || (render.height < 2) {
+    return Err(format!("{file}: The image must be at least 2x2 pixels.").into());
+  }
+  if render.samples_per_pixel == 0 {
+    return Err(format!("{file}: `samples_per_pixel` must be at least 1.").into());
+  }
+  if render.min_samples_per_pixel < 2 {
+    return Err(format!("{file}: `min_samples_per_pixel` must be at least 2.").into());
+  }
+
+  let materials = description
+    .materials
+    .iter()
+    .map(|(name, material)| (name.as_str(), material.build()))
+    .collect::<HashMap<_, _>>();
+
+  let mut objects = Vec::new();
+  let mut lights = Vec::new();
+  let mut inputs = Vec::new();
+  for object in &description.objects {
+    let line = line_of(source, object.span().start);
+    if let Some(name) = object
+      .get_ref()
+      .materials()
+      .into_iter()
+      .find(|name| !materials.contains_key(name))
+    {
+      return Err(
+        ParseError::new(
+          file,
+          line,
+          format!("Object refers to unknown material `{name}`."),
+        )
+        .into(),
+      );
+    }
+
+    object
+      .get_ref()
+      .build(
+        directory,
+        &materials,
+        &mut objects,
+        &mut lights,
+        &mut inputs,
+      )
+      .map_err(|err| match err.downcast::<ParseError>() {
+        Ok(err) => err as Box<dyn Error>,
+        Err(err) => ParseError::new(file, line, err.to_string()).into(),
+      })?;
+  }
+
+  let mut params = RenderParams {
+    debug: render.debug,
+    image_size: Vec2::new(render.width, render.height),
+    samples_per_pixel: render.samples_per_pixel,
+    adaptive_threshold: render.adaptive_threshold.map(|threshold| threshold.0),
+    min_samples_per_pixel: render.min_samples_per_pixel,
+    max_bounces: render.max_bounces,
+    roulette_depth: render.roulette_depth,
+    sampler: render.sampler,
+    integrator: render.integrator,
+    mis_heuristic: render.mis_heuristic,
+    occlusion_distance: render.occlusion_distance.0,
+    ..RenderParams::default()
+  };
+  configure(&mut params)?;
+
+  let environment = match &description.environment {
+    _ if params.debug => Environment::Debug,
+    Some(environment) => environment.build(directory, &mut inputs)?,
+    None => Environment::default(),
+  };
+
+  let camera = description
+    .camera
+    .get_ref()
+    .build(params.image_size.x() as f64 / params.image_size.y() as f64)
+    .map_err(|err| {
+      ParseError::new(
+        file,
+        line_of(source, description.camera.span().start),
+        err.to_string(),
+      )
+    })?;
+
+  Ok((
+    params,
+    Scene {
+      camera,
+      world: Box::new(Bvh::new(objects)),
+      environment,
+      lights,
+      inputs,
+    },
+  ))
+}
+
+pub fn load(
+  path: &Path,
+  configure: impl FnOnce(&mut RenderParams) -> Result<()>,
+) -> Result<(RenderParams, Scene)> {
+  let source = fs::read_to_string(path)
+    .map_err(|err| format!("Could not read scene {}: {err}", path.display()))?;
+
+  let (params, mut scene) = parse(
+    &path.display().to_string(),
+    &source,
+    path.parent().unwrap_or(Path::new("")),
+    configure,
+  )?;
+  scene.inputs.insert(0, path.to_owned());
+
+  Ok((params, scene))
+}
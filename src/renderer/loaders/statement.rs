@@ -0,0 +1,104 @@
+use crate::types::parse_error::ParseError;
+
+/// A line of a line-based text format such as OBJ or MTL, split into its
+/// keyword and arguments.
+pub struct Statement<'a> {
+  file: &'a str,
+  line: usize,
+  keyword: &'a str,
+  arguments: Vec<&'a str>,
+}
+
+/// Splits `source` into statements, skipping blank lines and `#` comments.
+pub fn statements<'a>(file: &'a str, source: &'a str) -> impl Iterator<Item = Statement<'a>> {
+  source.lines().enumerate().filter_map(move |(index, line)| {
+    let line_content = line.split('#').next().unwrap_or_default();
+    let mut words = line_content.split_whitespace();
+
+    words.next().map(|keyword| Statement {
+      file,
+      line: index + 1,
+      keyword,
+      arguments: words.collect(),
+    })
+  })
+}
+
+impl<'a> Statement<'a> {
+  pub fn keyword(&self) -> &'a str {
+    self.keyword
+  }
+
+  pub fn arguments(&self) -> &[&'a str] {
+    &self.arguments
+  }
+
+  pub fn error(&self, message: impl Into<String>) -> ParseError {
+    ParseError::new(self.file, self.line, message)
+  }
+
+  fn expect_arguments(&self, min: usize, max: usize) -> Result<(), ParseError> {
+    let count = self.arguments.len();
+
+    if (min..=max).contains(&count) {
+      Ok(())
+    } else if min == max {
+      Err(self.error(format!(
+        "`{}` expects {min} argument(s) but got {count}.",
+        self.keyword
+      )))
+    } else {
+      Err(self.error(format!(
+        "`{}` expects {min} to {max} arguments but got {count}.",
+        self.keyword
+      )))
+    }
+  }
+
+  /// Parses between `min` and `max` numeric arguments.
+  pub fn floats(&self, min: usize, max: usize) -> Result<Vec<f64>, ParseError> {
+    self.expect_arguments(min, max)?;
+
+    self
+      .arguments
+      .iter()
+      .map(|argument| {
+        argument
+          .parse::<f64>()
+          .ok()
+          .filter(|value| value.is_finite())
+          .ok_or_else(|| {
+            self.error(format!(
+              "Invalid number `{argument}` in `{}` statement.",
+              self.keyword
+            ))
+          })
+      })
+      .collect()
+  }
+
+  pub fn float(&self) -> Result<f64, ParseError> {
+    Ok(self.floats(1, 1)?[0])
+  }
+
+  pub fn integer(&self) -> Result<usize, ParseError> {
+    self.expect_arguments(1, 1)?;
+
+    self.arguments[0].parse().map_err(|_| {
+      self.error(format!(
+        "Invalid integer `{}` in `{}` statement.",
+        self.arguments[0], self.keyword
+      ))
+    })
+  }
+
+  /// Joins all arguments, for statements naming something which may contain
+  /// spaces.
+  pub fn name(&self) -> Result<String, ParseError> {
+    if self.arguments.is_empty() {
+      Err(self.error(format!("`{}` expects a name.", self.keyword)))
+    } else {
+      Ok(self.arguments.join(" "))
+    }
+  }
+}
@@ -1,8 +1,8 @@
 use crate::renderer::core::color::COLOR_WHITE;
-use crate::renderer::core::random;
 use crate::renderer::core::ray::Ray;
 use crate::renderer::core::vec3::Vec3;
 use crate::renderer::materials::material::{Material, Scatter};
+use crate::renderer::samplers::sampler::Sampler;
 use crate::renderer::scene::hittable::{Face, Hit};
 
 fn reflectance(cos_theta: f64, refraction_ratio: f64) -> f64 {
@@ -28,7 +28,7 @@ impl Dielectric {
 }
 
 impl Material for Dielectric {
-  fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<Scatter> {
+  fn scatter(&self, ray: &Ray, hit: &Hit, sampler: &mut dyn Sampler) -> Option<Scatter> {
     let unit_direction = ray.direction().unit();
     let normal = hit.normal();
     let refraction_ratio = {
@@ -43,7 +43,7 @@ impl Material for Dielectric {
     let sin_theta = (1.0 - cos_theta.powi(2)).sqrt();
 
     let can_refract = (refraction_ratio * sin_theta) <= 1.0;
-    let should_reflect = reflectance(cos_theta, refraction_ratio) > random::random(0.0..1.0);
+    let should_reflect = reflectance(cos_theta, refraction_ratio) > sampler.get_1d();
 
     let direction = if can_refract && !should_reflect {
       refract(unit_direction, normal, cos_theta, refraction_ratio)
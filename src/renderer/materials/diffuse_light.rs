@@ -0,0 +1,31 @@
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec3::Color;
+use crate::renderer::materials::material::{Material, Scatter};
+use crate::renderer::samplers::sampler::Sampler;
+use crate::renderer::scene::hittable::Hit;
+
+/// An area light emitting `emit` evenly in every direction, from both sides of
+/// the surface.
+pub struct DiffuseLight {
+  emit: Color,
+}
+
+impl DiffuseLight {
+  pub fn new(emit: Color) -> Self {
+    Self { emit }
+  }
+}
+
+impl Material for DiffuseLight {
+  fn scatter(&self, _ray: &Ray, _hit: &Hit, _sampler: &mut dyn Sampler) -> Option<Scatter> {
+    None
+  }
+
+  fn emitted(&self, _hit: &Hit) -> Color {
+    self.emit
+  }
+
+  fn is_emissive(&self) -> bool {
+    !self.emit.near_zero()
+  }
+}
@@ -1,8 +1,10 @@
 use crate::renderer::core::diffuse::{self, DiffuseMethod};
 use crate::renderer::core::ray::Ray;
-use crate::renderer::core::vec3::Color;
+use crate::renderer::core::vec3::{Color, Vec3};
 use crate::renderer::materials::material::{Material, Scatter};
+use crate::renderer::samplers::sampler::Sampler;
 use crate::renderer::scene::hittable::Hit;
+use std::f64::consts::PI;
 
 pub struct Lambertian {
   albedo: Color,
@@ -15,13 +17,24 @@ impl Lambertian {
 }
 
 impl Material for Lambertian {
-  fn scatter(&self, _ray: &Ray, hit: &Hit) -> Option<Scatter> {
-    Some(Scatter::new(
-      Ray::new(
-        hit.point(),
-        diffuse::bounce_direction(&hit.normal(), DiffuseMethod::TrueLambertian),
-      ),
+  fn scatter(&self, ray: &Ray, hit: &Hit, sampler: &mut dyn Sampler) -> Option<Scatter> {
+    let direction =
+      diffuse::bounce_direction(&hit.normal(), DiffuseMethod::TrueLambertian, sampler);
+    let pdf = self.scattering_pdf(ray, hit, &direction);
+
+    Some(Scatter::diffuse(
+      Ray::new(hit.point(), direction),
       self.albedo,
+      pdf,
     ))
   }
+
+  fn evaluate(&self, ray: &Ray, hit: &Hit, direction: &Vec3) -> Color {
+    self.albedo * self.scattering_pdf(ray, hit, direction)
+  }
+
+  /// Directions are cosine-distributed about the normal.
+  fn scattering_pdf(&self, _ray: &Ray, hit: &Hit, direction: &Vec3) -> f64 {
+    (hit.normal().dot(&direction.unit()) / PI).max(0.0)
+  }
 }
@@ -1,15 +1,32 @@
 use crate::renderer::core::ray::Ray;
-use crate::renderer::core::vec3::Color;
+use crate::renderer::core::vec3::{Color, Vec3};
+use crate::renderer::samplers::sampler::Sampler;
 use crate::renderer::scene::hittable::Hit;
 
 pub struct Scatter {
   ray: Ray,
   attenuation: Color,
+  pdf: Option<f64>,
 }
 
 impl Scatter {
+  /// A scattered ray in a single direction, such as a mirror reflection.
   pub fn new(ray: Ray, attenuation: Color) -> Self {
-    Self { ray, attenuation }
+    Self {
+      ray,
+      attenuation,
+      pdf: None,
+    }
+  }
+
+  /// A scattered ray picked with density `pdf` over solid angle from a
+  /// distribution of directions, which lights can also be sampled against.
+  pub fn diffuse(ray: Ray, attenuation: Color, pdf: f64) -> Self {
+    Self {
+      ray,
+      attenuation,
+      pdf: Some(pdf),
+    }
   }
 
   pub fn ray(&self) -> &Ray {
@@ -19,8 +36,37 @@ impl Scatter {
   pub fn attenuation(&self) -> Color {
     self.attenuation
   }
+
+  /// The density of the scattered direction, or `None` if it could not have
+  /// gone anywhere else.
+  pub fn pdf(&self) -> Option<f64> {
+    self.pdf
+  }
 }
 
 pub trait Material: Send + Sync {
-  fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<Scatter>;
+  fn scatter(&self, ray: &Ray, hit: &Hit, sampler: &mut dyn Sampler) -> Option<Scatter>;
+
+  /// The fraction of light arriving along `direction` which is scattered back
+  /// along `ray`, including the cosine at the surface. Only needed by
+  /// materials which make diffuse scatters.
+  fn evaluate(&self, _ray: &Ray, _hit: &Hit, _direction: &Vec3) -> Color {
+    Color::default()
+  }
+
+  /// The density over solid angle with which `scatter` picks `direction`.
+  fn scattering_pdf(&self, _ray: &Ray, _hit: &Hit, _direction: &Vec3) -> f64 {
+    0.0
+  }
+
+  /// Light given off by the material at `hit`.
+  fn emitted(&self, _hit: &Hit) -> Color {
+    Color::default()
+  }
+
+  /// Whether the material gives off light, so that objects made of it should be
+  /// sampled as lights.
+  fn is_emissive(&self) -> bool {
+    false
+  }
 }
@@ -2,6 +2,7 @@ use crate::renderer::core::diffuse;
 use crate::renderer::core::ray::Ray;
 use crate::renderer::core::vec3::Color;
 use crate::renderer::materials::material::{Material, Scatter};
+use crate::renderer::samplers::sampler::Sampler;
 use crate::renderer::scene::hittable::Hit;
 
 pub struct Metal {
@@ -19,11 +20,11 @@ impl Metal {
 }
 
 impl Material for Metal {
-  fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<Scatter> {
+  fn scatter(&self, ray: &Ray, hit: &Hit, sampler: &mut dyn Sampler) -> Option<Scatter> {
     let reflected_ray = ray.direction().unit().reflect(&hit.normal());
     let scattered_ray = Ray::new(
       hit.point(),
-      reflected_ray + (self.fuzziness * diffuse::random_point_in_unit_sphere()),
+      reflected_ray + (self.fuzziness * diffuse::random_point_in_unit_sphere(sampler)),
     );
 
     if scattered_ray.direction().dot(&hit.normal()) > 0.0 {
@@ -1,69 +1,139 @@
-use crate::renderer::core::color::{
-  COLOR_BLUE, COLOR_GREEN, COLOR_LIGHT_BLUE, COLOR_RED, COLOR_WHITE, COLOR_YELLOW,
-};
-use crate::renderer::core::math::{self, Range};
-use crate::renderer::core::ray::Ray;
+use crate::renderer::core::statistics::RunningStats;
 use crate::renderer::core::vec2::Vec2;
-use crate::renderer::core::vec3::Color;
-use crate::renderer::core::{color, random};
+use crate::renderer::core::vec3::{Point3, Vec3};
+use crate::renderer::integrators::integrator::IntegratorKind;
+use crate::renderer::samplers::sampler::SamplerKind;
 use crate::renderer::scene::camera::Camera;
+use crate::renderer::scene::environment::Environment;
 use crate::renderer::scene::hittable::Hittable;
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::Arc;
 
-#[derive(Default)]
+/// How multiple importance sampling weighs light sampling against BSDF
+/// sampling.
+#[derive(Default, Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MisHeuristic {
+  Balance,
+  #[default]
+  Power,
+}
+
+impl MisHeuristic {
+  /// Weighs a sample taken with density `pdf` against another strategy which
+  /// could have taken it with density `other_pdf`.
+  pub fn weight(self, pdf: f64, other_pdf: f64) -> f64 {
+    if other_pdf == 0.0 {
+      return 1.0;
+    }
+
+    let (pdf, other_pdf) = match self {
+      MisHeuristic::Balance => (pdf, other_pdf),
+      MisHeuristic::Power => (pdf * pdf, other_pdf * other_pdf),
+    };
+    pdf / (pdf + other_pdf)
+  }
+}
+
+#[derive(Default, Clone, Debug)]
 pub struct RenderParams {
   pub debug: bool,
-  pub last_pixel: Vec2<usize>,
+  pub image_size: Vec2<usize>,
+  /// The number of samples per pixel, or the most taken with adaptive
+  /// sampling.
   pub samples_per_pixel: usize,
+  /// Stops sampling a pixel once the relative standard error of its luminance
+  /// falls to this, if set.
+  pub adaptive_threshold: Option<f64>,
+  /// The number of samples taken before a pixel may be judged converged.
+  pub min_samples_per_pixel: usize,
   pub max_bounces: usize,
+  /// The number of bounces after which paths may be ended by Russian roulette.
+  pub roulette_depth: usize,
+  /// Seeds the random numbers of every sample, so that the same seed gives
+  /// the same image.
+  pub seed: u64,
+  pub sampler: SamplerKind,
+  pub integrator: IntegratorKind,
+  pub mis_heuristic: MisHeuristic,
+  /// How far away surfaces occlude each other for ambient occlusion.
+  pub occlusion_distance: f64,
 }
 
 pub struct Scene {
   pub camera: Camera,
   pub world: Box<dyn Hittable>,
+  pub environment: Environment,
+  /// The emissive objects of `world`, sampled directly at diffuse hits.
+  pub lights: Vec<Arc<dyn Hittable>>,
+  /// The files the scene was loaded from.
+  pub inputs: Vec<PathBuf>,
 }
 
-pub fn render_pixel(params: &RenderParams, scene: &Scene, pixel: Vec2<usize>) -> Color {
-  let mut pixel_color = Color::default();
+impl RenderParams {
+  pub fn last_pixel(&self) -> Vec2<usize> {
+    Vec2::new(self.image_size.x() - 1, self.image_size.y() - 1)
+  }
 
-  for _ in 0..params.samples_per_pixel {
-    let u = (pixel.x() as f64 + random::random(0.0..1.0)) / params.last_pixel.x() as f64;
-    let v = (pixel.y() as f64 + random::random(0.0..1.0)) / params.last_pixel.y() as f64;
+  /// Whether adaptive sampling can stop sampling a pixel with `stats`.
+  fn converged(&self, stats: &RunningStats) -> bool {
+    self.adaptive_threshold.is_some_and(|threshold| {
+      (stats.count() >= self.min_samples_per_pixel) && (stats.relative_error() <= threshold)
+    })
+  }
+}
 
-    let ray = scene.camera.get_ray((u, v));
+impl Scene {
+  /// The density over solid angle with which light sampling picks `direction`
+  /// from `origin`, with each light equally likely to be chosen.
+  pub fn light_pdf(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    if self.lights.is_empty() {
+      return 0.0;
+    }
 
-    pixel_color += ray_color(&ray, scene.world.as_ref(), params.max_bounces, params.debug);
+    self
+      .lights
+      .iter()
+      .map(|light| light.pdf(origin, direction))
+      .sum::<f64>()
+      / self.lights.len() as f64
   }
-
-  color::calculate_color(pixel_color, params.samples_per_pixel)
 }
 
-fn ray_color(
-  ray: &Ray,
-  world: &dyn Hittable,
-  bounce_depth: usize,
-  debug_background: bool,
-) -> Color {
-  if bounce_depth == 0 {
-    Color::default()
-  } else if let Some(hit) = world.hit(ray, Range::new(0.001, f64::INFINITY)) {
-    if let Some(scatter) = hit.material().scatter(ray, &hit) {
-      scatter.attenuation() * ray_color(scatter.ray(), world, bounce_depth - 1, debug_background)
-    } else {
-      Color::default()
-    }
-  } else {
-    let direction = ray.direction().unit();
+/// Adds samples of the linear radiance arriving through `pixel` to `stats`,
+/// until it holds `samples` samples or converges. Each sample only depends on
+/// its index, so a pixel sampled in several goes ends up the same as one
+/// sampled in one go.
+pub fn render_pixel(
+  params: &RenderParams,
+  scene: &Scene,
+  pixel: Vec2<usize>,
+  stats: &mut RunningStats,
+  samples: usize,
+) {
+  let last_pixel = params.last_pixel();
 
-    if debug_background {
-      match (direction.x() >= 0.0, direction.y() >= 0.0) {
-        (true, true) => COLOR_RED,
-        (true, false) => COLOR_GREEN,
-        (false, true) => COLOR_BLUE,
-        (false, false) => COLOR_YELLOW,
-      }
-    } else {
-      let time = math::map_range(direction.y(), Range::new(-1.0, 1.0), Range::new(0.0, 1.0));
-      color::linear_blend(COLOR_LIGHT_BLUE, COLOR_WHITE, time)
-    }
+  let pixel_index = (pixel.y() * params.image_size.x()) + pixel.x();
+
+  while (stats.count() < samples) && !params.converged(stats) {
+    let sample = stats.count();
+    let mut sampler =
+      params
+        .sampler
+        .sampler(params.seed, pixel_index, sample, params.samples_per_pixel);
+
+    let (jitter_x, jitter_y) = sampler.get_2d();
+    let u = (pixel.x() as f64 + jitter_x) / last_pixel.x() as f64;
+    let v = (pixel.y() as f64 + jitter_y) / last_pixel.y() as f64;
+
+    let ray = scene.camera.get_ray((u, v), sampler.as_mut());
+
+    stats.add(
+      params
+        .integrator
+        .integrator()
+        .radiance(&ray, scene, params, sampler.as_mut()),
+    );
   }
 }
@@ -0,0 +1,5 @@
+pub mod halton;
+pub mod independent;
+pub mod sampler;
+pub mod sobol;
+pub mod stratified;
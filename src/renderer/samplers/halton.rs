@@ -0,0 +1,71 @@
+use crate::renderer::core::random::{self, Pcg32};
+use crate::renderer::samplers::sampler::{Sampler, ONE_MINUS_EPSILON};
+
+/// The bases of the dimensions of the Halton sequence. Dimensions past these
+/// are filled with independent random numbers.
+const PRIMES: [u64; 64] = [
+  2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+  101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+  197, 199, 211, 223, 227, 229, 233, 239, 241, 251, 257, 263, 269, 271, 277, 281, 283, 293, 307,
+  311,
+];
+
+/// The radical inverse of `index` in `base`, with each digit shifted by its
+/// own random amount chosen by `seed` (random-digit scrambling).
+fn scrambled_radical_inverse(base: u64, mut index: u64, seed: u64) -> f64 {
+  let inverse_base = 1.0 / base as f64;
+  let mut weight = inverse_base;
+  let mut value = 0.0;
+  let mut digit_index = 0;
+
+  // Digits beyond the index's are scrambled too, which fills the space
+  // between the unscrambled points.
+  while weight > f64::EPSILON {
+    let digit = index % base;
+    let shift = random::hash(seed, digit_index) % base;
+
+    value += ((digit + shift) % base) as f64 * weight;
+    index /= base;
+    weight *= inverse_base;
+    digit_index += 1;
+  }
+
+  value.min(ONE_MINUS_EPSILON)
+}
+
+/// Takes dimension `d` of sample `i` from the radical inverse of `i` in the
+/// `d`th prime base, scrambled differently in every pixel.
+pub struct HaltonSampler {
+  pixel_seed: u64,
+  sample: u64,
+  dimension: usize,
+  rng: Pcg32,
+}
+
+impl HaltonSampler {
+  pub fn new(seed: u64, pixel: usize, sample: usize) -> Self {
+    Self {
+      pixel_seed: random::hash(seed, pixel as u64),
+      sample: sample as u64,
+      dimension: 0,
+      rng: Pcg32::for_sample(seed, pixel, sample),
+    }
+  }
+}
+
+impl Sampler for HaltonSampler {
+  fn get_1d(&mut self) -> f64 {
+    let Some(&base) = PRIMES.get(self.dimension) else {
+      return self.rng.next_f64();
+    };
+
+    let seed = random::hash(self.pixel_seed, self.dimension as u64);
+    self.dimension += 1;
+
+    scrambled_radical_inverse(base, self.sample, seed)
+  }
+
+  fn get_2d(&mut self) -> (f64, f64) {
+    (self.get_1d(), self.get_1d())
+  }
+}
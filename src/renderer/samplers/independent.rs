@@ -0,0 +1,25 @@
+use crate::renderer::core::random::Pcg32;
+use crate::renderer::samplers::sampler::Sampler;
+
+/// Every dimension is an independent uniform random number.
+pub struct IndependentSampler {
+  rng: Pcg32,
+}
+
+impl IndependentSampler {
+  pub fn new(seed: u64, pixel: usize, sample: usize) -> Self {
+    Self {
+      rng: Pcg32::for_sample(seed, pixel, sample),
+    }
+  }
+}
+
+impl Sampler for IndependentSampler {
+  fn get_1d(&mut self) -> f64 {
+    self.rng.next_f64()
+  }
+
+  fn get_2d(&mut self) -> (f64, f64) {
+    (self.rng.next_f64(), self.rng.next_f64())
+  }
+}
@@ -0,0 +1,63 @@
+use crate::renderer::samplers::halton::HaltonSampler;
+use crate::renderer::samplers::independent::IndependentSampler;
+use crate::renderer::samplers::sobol::SobolSampler;
+use crate::renderer::samplers::stratified::StratifiedSampler;
+use clap::ValueEnum;
+use serde::Deserialize;
+
+/// The largest `f64` below 1.
+pub const ONE_MINUS_EPSILON: f64 = 1.0 - (f64::EPSILON / 2.0);
+
+/// The random numbers of one sample of a pixel, handed out one dimension at a
+/// time: first the position within the pixel, then the lens, then whatever
+/// each bounce needs. Samplers other than the independent one spread the
+/// samples of a pixel evenly over each dimension, so that images converge
+/// faster.
+pub trait Sampler {
+  /// The next dimension of the sample, in [0, 1).
+  fn get_1d(&mut self) -> f64;
+
+  /// The next two dimensions of the sample, in [0, 1)^2, spread evenly as a
+  /// pair.
+  fn get_2d(&mut self) -> (f64, f64);
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum SamplerKind {
+  /// Independent uniform random numbers.
+  #[default]
+  Independent,
+  /// One jittered sample in each stratum of every dimension.
+  Stratified,
+  /// The scrambled Halton sequence.
+  Halton,
+  /// The Owen-scrambled Sobol' sequence, best with a power of two samples per
+  /// pixel.
+  Sobol,
+}
+
+impl SamplerKind {
+  /// The sampler for sample `sample` of the `samples_per_pixel` taken for the
+  /// pixel with index `pixel`.
+  pub fn sampler(
+    self,
+    seed: u64,
+    pixel: usize,
+    sample: usize,
+    samples_per_pixel: usize,
+  ) -> Box<dyn Sampler> {
+    match self {
+      SamplerKind::Independent => Box::new(IndependentSampler::new(seed, pixel, sample)),
+      SamplerKind::Stratified => Box::new(StratifiedSampler::new(
+        seed,
+        pixel,
+        sample,
+        samples_per_pixel,
+      )),
+      SamplerKind::Halton => Box::new(HaltonSampler::new(seed, pixel, sample)),
+      SamplerKind::Sobol => Box::new(SobolSampler::new(seed, pixel, sample)),
+    }
+  }
+}
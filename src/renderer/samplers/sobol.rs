@@ -0,0 +1,94 @@
+use crate::renderer::core::random;
+use crate::renderer::samplers::sampler::Sampler;
+
+/// Laine and Karras' hash, which only lets each bit of `value` affect the bits
+/// above it.
+fn laine_karras_permutation(mut value: u32, seed: u32) -> u32 {
+  value = value.wrapping_add(seed);
+  value ^= value.wrapping_mul(0x6c50b47c);
+  value ^= value.wrapping_mul(0xb82f1e52);
+  value ^= value.wrapping_mul(0xc7afe638);
+  value ^= value.wrapping_mul(0x8d22f6e6);
+  value
+}
+
+/// Owen-scrambles the binary fraction `value`: every bit is flipped depending
+/// on the bits before it (Burley's "Practical Hash-based Owen Scrambling").
+fn owen_scramble(value: u32, seed: u32) -> u32 {
+  laine_karras_permutation(value.reverse_bits(), seed).reverse_bits()
+}
+
+/// The first dimension of the Sobol' sequence, whose generator matrix is the
+/// identity.
+fn sobol_first(index: u32) -> u32 {
+  index.reverse_bits()
+}
+
+/// The second dimension of the Sobol' sequence, whose generator matrix is
+/// Pascal's triangle modulo 2.
+fn sobol_second(mut index: u32) -> u32 {
+  let mut column = 1 << 31;
+  let mut value = 0;
+
+  while index != 0 {
+    if index & 1 != 0 {
+      value ^= column;
+    }
+
+    index >>= 1;
+    column ^= column >> 1;
+  }
+
+  value
+}
+
+fn to_fraction(value: u32) -> f64 {
+  value as f64 / (1u64 << 32) as f64
+}
+
+/// Fills every pair of dimensions with the first two dimensions of the Sobol'
+/// sequence ("padding"), Owen-scrambled and shuffled differently in each pair
+/// and pixel so that the pairs are not correlated with each other.
+pub struct SobolSampler {
+  pixel_seed: u64,
+  sample: u32,
+  dimension: u64,
+}
+
+impl SobolSampler {
+  pub fn new(seed: u64, pixel: usize, sample: usize) -> Self {
+    Self {
+      pixel_seed: random::hash(seed, pixel as u64),
+      sample: sample as u32,
+      dimension: 0,
+    }
+  }
+
+  /// The seed of the next dimension, and this sample's shuffled index in it.
+  fn next_dimension(&mut self) -> (u64, u32) {
+    let seed = random::hash(self.pixel_seed, self.dimension);
+    self.dimension += 1;
+
+    (seed, owen_scramble(self.sample, seed as u32))
+  }
+}
+
+impl Sampler for SobolSampler {
+  fn get_1d(&mut self) -> f64 {
+    let (seed, index) = self.next_dimension();
+
+    to_fraction(owen_scramble(sobol_first(index), (seed >> 32) as u32))
+  }
+
+  fn get_2d(&mut self) -> (f64, f64) {
+    let (seed, index) = self.next_dimension();
+
+    (
+      to_fraction(owen_scramble(sobol_first(index), (seed >> 32) as u32)),
+      to_fraction(owen_scramble(
+        sobol_second(index),
+        random::hash(seed, 1) as u32,
+      )),
+    )
+  }
+}
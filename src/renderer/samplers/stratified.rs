@@ -0,0 +1,54 @@
+use crate::renderer::core::random::{self, Pcg32};
+use crate::renderer::samplers::sampler::{Sampler, ONE_MINUS_EPSILON};
+
+/// Splits every dimension into as many strata as there are samples per pixel
+/// (a grid of them for 2D dimensions) and jitters each sample within its own
+/// stratum. The strata are visited in a different random order for each
+/// dimension, so that dimensions are not correlated with each other.
+pub struct StratifiedSampler {
+  pixel_seed: u64,
+  sample: u32,
+  samples_per_pixel: u32,
+  dimension: u64,
+  rng: Pcg32,
+}
+
+impl StratifiedSampler {
+  pub fn new(seed: u64, pixel: usize, sample: usize, samples_per_pixel: usize) -> Self {
+    Self {
+      pixel_seed: random::hash(seed, pixel as u64),
+      sample: sample as u32,
+      samples_per_pixel: samples_per_pixel as u32,
+      dimension: 0,
+      rng: Pcg32::for_sample(seed, pixel, sample),
+    }
+  }
+
+  /// The stratum of this sample, out of `count`, in the next dimension.
+  fn stratum(&mut self, count: u32) -> u32 {
+    let seed = random::hash(self.pixel_seed, self.dimension);
+    self.dimension += 1;
+
+    random::permutation_element(self.sample % count, count, seed as u32)
+  }
+}
+
+impl Sampler for StratifiedSampler {
+  fn get_1d(&mut self) -> f64 {
+    let count = self.samples_per_pixel;
+    let stratum = self.stratum(count);
+
+    ((stratum as f64 + self.rng.next_f64()) / count as f64).min(ONE_MINUS_EPSILON)
+  }
+
+  fn get_2d(&mut self) -> (f64, f64) {
+    let columns = (self.samples_per_pixel as f64).sqrt() as u32;
+    let rows = self.samples_per_pixel.div_ceil(columns);
+    let stratum = self.stratum(columns * rows);
+
+    let x = ((stratum % columns) as f64 + self.rng.next_f64()) / columns as f64;
+    let y = ((stratum / columns) as f64 + self.rng.next_f64()) / rows as f64;
+
+    (x.min(ONE_MINUS_EPSILON), y.min(ONE_MINUS_EPSILON))
+  }
+}
@@ -1,3 +1,15 @@
+pub mod bvh;
 pub mod camera;
+pub mod csg;
+pub mod cuboid;
+pub mod disk;
+pub mod environment;
 pub mod hittable;
+pub mod instance;
+pub mod mesh;
+pub mod plane;
+pub mod quad;
+pub mod quadric;
 pub mod sphere;
+pub mod torus;
+pub mod triangle;
@@ -0,0 +1,308 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::math::Range;
+use crate::renderer::core::ray::Ray;
+use crate::renderer::scene::hittable::{self, Hit, Hittable};
+use std::sync::Arc;
+
+/// Number of buckets the centroids are binned into when evaluating split
+/// candidates.
+const SAH_BUCKETS: usize = 12;
+/// Cost of visiting a node relative to intersecting a single object.
+const TRAVERSAL_COST: f64 = 0.125;
+const MAX_OBJECTS_PER_LEAF: usize = 4;
+
+struct BvhObject {
+  hittable: Arc<dyn Hittable>,
+  bounding_box: Aabb,
+  centroid: f64,
+}
+
+enum BvhNode {
+  Leaf {
+    bounding_box: Aabb,
+    objects: Vec<Arc<dyn Hittable>>,
+  },
+  Branch {
+    bounding_box: Aabb,
+    axis: usize,
+    left: Box<BvhNode>,
+    right: Box<BvhNode>,
+  },
+}
+
+struct Split {
+  axis: usize,
+  position: f64,
+  cost: f64,
+}
+
+fn surrounding(objects: &[BvhObject]) -> Aabb {
+  objects
+    .iter()
+    .skip(1)
+    .fold(objects[0].bounding_box, |aabb, object| {
+      aabb.surrounding(&object.bounding_box)
+    })
+}
+
+fn merge(first: Option<Aabb>, second: Option<Aabb>) -> Option<Aabb> {
+  match (first, second) {
+    (Some(first), Some(second)) => Some(first.surrounding(&second)),
+    (first, second) => first.or(second),
+  }
+}
+
+fn set_centroids(objects: &mut [BvhObject], axis: usize) {
+  for object in objects {
+    object.centroid = object.bounding_box.centroid().axis(axis);
+  }
+}
+
+/// Finds the cheapest bucketed split along any axis according to the surface
+/// area heuristic.
+fn find_split(objects: &mut [BvhObject], centroid_bounds: &Aabb) -> Option<Split> {
+  let mut best: Option<Split> = None;
+
+  for axis in 0..3 {
+    let min = centroid_bounds.min().axis(axis);
+    let extent = centroid_bounds.extent().axis(axis);
+
+    if extent <= 0.0 {
+      continue;
+    }
+
+    set_centroids(objects, axis);
+
+    let bucket_of = |centroid: f64| {
+      (((centroid - min) / extent) * SAH_BUCKETS as f64).min((SAH_BUCKETS - 1) as f64) as usize
+    };
+
+    let mut counts = [0usize; SAH_BUCKETS];
+    let mut bounds: [Option<Aabb>; SAH_BUCKETS] = [None; SAH_BUCKETS];
+
+    for object in objects.iter() {
+      let bucket = bucket_of(object.centroid);
+      counts[bucket] += 1;
+      bounds[bucket] = merge(bounds[bucket], Some(object.bounding_box));
+    }
+
+    // Sweep from the right to find the cost of every suffix of buckets, then from
+    // the left to combine each prefix with its complementary suffix.
+    let mut right_costs = [0.0; SAH_BUCKETS];
+    let mut count = 0;
+    let mut aabb: Option<Aabb> = None;
+    for bucket in (1..SAH_BUCKETS).rev() {
+      count += counts[bucket];
+      aabb = merge(aabb, bounds[bucket]);
+      right_costs[bucket] = aabb.map_or(0.0, |aabb| count as f64 * aabb.surface_area());
+    }
+
+    count = 0;
+    aabb = None;
+    for boundary in 1..SAH_BUCKETS {
+      count += counts[boundary - 1];
+      aabb = merge(aabb, bounds[boundary - 1]);
+      let cost =
+        aabb.map_or(0.0, |aabb| count as f64 * aabb.surface_area()) + right_costs[boundary];
+
+      if best.as_ref().is_none_or(|best| cost < best.cost) {
+        best = Some(Split {
+          axis,
+          position: min + (extent * (boundary as f64 / SAH_BUCKETS as f64)),
+          cost,
+        });
+      }
+    }
+  }
+
+  best
+}
+
+impl BvhNode {
+  fn build(mut objects: Vec<BvhObject>) -> BvhNode {
+    let bounding_box = surrounding(&objects);
+    let centroid_bounds = {
+      let centroids = objects
+        .iter()
+        .map(|object| object.bounding_box.centroid())
+        .collect::<Vec<_>>();
+      Aabb::from_points(&centroids)
+    };
+
+    let leaf = |objects: Vec<BvhObject>| BvhNode::Leaf {
+      bounding_box,
+      objects: objects.into_iter().map(|object| object.hittable).collect(),
+    };
+
+    if objects.len() == 1 {
+      return leaf(objects);
+    }
+
+    let split = find_split(&mut objects, &centroid_bounds);
+    let leaf_cost = objects.len() as f64;
+
+    let (axis, mut right) = match split {
+      Some(split) => {
+        let split_cost =
+          TRAVERSAL_COST + (split.cost / bounding_box.surface_area().max(f64::MIN_POSITIVE));
+
+        if (split_cost >= leaf_cost) && (objects.len() <= MAX_OBJECTS_PER_LEAF) {
+          return leaf(objects);
+        }
+
+        set_centroids(&mut objects, split.axis);
+        let (left, right) = objects
+          .into_iter()
+          .partition::<Vec<_>, _>(|object| object.centroid < split.position);
+        objects = left;
+
+        (split.axis, right)
+      }
+      None if objects.len() <= MAX_OBJECTS_PER_LEAF => return leaf(objects),
+      // Every centroid coincides, so split the objects in half arbitrarily.
+      None => {
+        let right = objects.split_off(objects.len() / 2);
+        (centroid_bounds.longest_axis(), right)
+      }
+    };
+
+    if objects.is_empty() {
+      objects = right.split_off(right.len() / 2);
+    } else if right.is_empty() {
+      right = objects.split_off(objects.len() / 2);
+    }
+
+    BvhNode::Branch {
+      bounding_box,
+      axis,
+      left: Box::new(BvhNode::build(objects)),
+      right: Box::new(BvhNode::build(right)),
+    }
+  }
+
+  fn bounding_box(&self) -> &Aabb {
+    match self {
+      BvhNode::Leaf { bounding_box, .. } | BvhNode::Branch { bounding_box, .. } => bounding_box,
+    }
+  }
+
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    if !self.bounding_box().hit(ray, hittable_range) {
+      return None;
+    }
+
+    match self {
+      BvhNode::Leaf { objects, .. } => objects.hit(ray, hittable_range),
+      BvhNode::Branch {
+        axis, left, right, ..
+      } => {
+        let (near, far) = if ray.direction().axis(*axis) < 0.0 {
+          (right, left)
+        } else {
+          (left, right)
+        };
+
+        match near.hit(ray, hittable_range) {
+          Some(near_hit) => far
+            .hit(ray, Range::new(hittable_range.start(), near_hit.time()))
+            .or(Some(near_hit)),
+          None => far.hit(ray, hittable_range),
+        }
+      }
+    }
+  }
+
+  fn is_solid(&self) -> bool {
+    match self {
+      BvhNode::Leaf { objects, .. } => objects.is_solid(),
+      BvhNode::Branch { left, right, .. } => left.is_solid() && right.is_solid(),
+    }
+  }
+
+  /// Adds every crossing within `hittable_range` to `hits`, unordered.
+  fn hits(&self, ray: &Ray, hittable_range: Range<f64>, hits: &mut Vec<Hit>) {
+    if !self.bounding_box().hit(ray, hittable_range) {
+      return;
+    }
+
+    match self {
+      BvhNode::Leaf { objects, .. } => {
+        for object in objects {
+          hits.extend(object.hits(ray, hittable_range));
+        }
+      }
+      BvhNode::Branch { left, right, .. } => {
+        left.hits(ray, hittable_range, hits);
+        right.hits(ray, hittable_range, hits);
+      }
+    }
+  }
+}
+
+/// A bounding volume hierarchy over a set of objects, partitioned with the
+/// surface area heuristic. Objects without a bounding box are kept aside and
+/// tested against every ray.
+pub struct Bvh {
+  root: Option<BvhNode>,
+  unbounded: Vec<Arc<dyn Hittable>>,
+}
+
+impl Bvh {
+  pub fn new(objects: Vec<Arc<dyn Hittable>>) -> Self {
+    let mut bounded = Vec::with_capacity(objects.len());
+    let mut unbounded = Vec::new();
+
+    for hittable in objects {
+      match hittable.bounding_box() {
+        Some(bounding_box) => bounded.push(BvhObject {
+          hittable,
+          bounding_box,
+          centroid: 0.0,
+        }),
+        None => unbounded.push(hittable),
+      }
+    }
+
+    Self {
+      root: (!bounded.is_empty()).then(|| BvhNode::build(bounded)),
+      unbounded,
+    }
+  }
+}
+
+impl Hittable for Bvh {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    let unbounded_hit = self.unbounded.hit(ray, hittable_range);
+    let range = unbounded_hit.as_ref().map_or(hittable_range, |hit| {
+      Range::new(hittable_range.start(), hit.time())
+    });
+
+    self
+      .root
+      .as_ref()
+      .and_then(|root| root.hit(ray, range))
+      .or(unbounded_hit)
+  }
+
+  fn hits(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Hit> {
+    let mut hits = self.unbounded.hits(ray, hittable_range);
+    if let Some(root) = &self.root {
+      root.hits(ray, hittable_range, &mut hits);
+    }
+
+    hittable::sort_by_time(&mut hits);
+    hits
+  }
+
+  fn is_solid(&self) -> bool {
+    self.unbounded.is_solid() && self.root.as_ref().is_none_or(BvhNode::is_solid)
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    if self.unbounded.is_empty() {
+      self.root.as_ref().map(|root| *root.bounding_box())
+    } else {
+      None
+    }
+  }
+}
@@ -1,38 +1,74 @@
+use crate::renderer::core::diffuse;
 use crate::renderer::core::ray::Ray;
 use crate::renderer::core::vec3::{Point3, Vec3};
+use crate::renderer::samplers::sampler::Sampler;
 
 pub struct Camera {
   origin: Point3,
   lower_left_corner: Point3,
   horizontal: Vec3,
   vertical: Vec3,
+  u: Vec3,
+  v: Vec3,
+  lens_radius: f64,
 }
 
 impl Camera {
-  pub fn new(aspect_ratio: f64) -> Self {
-    let viewport_height = 2.0;
+  /// Creates a camera at `look_from` pointed towards `look_at`, rolled so that
+  /// `view_up` points upwards in the image. `vertical_fov` is the vertical
+  /// field of view in degrees.
+  ///
+  /// Objects at `focus_distance` from the camera are in perfect focus, and
+  /// everything else is blurred according to `aperture_radius`, the radius of
+  /// the lens. An aperture of zero is a pinhole camera.
+  pub fn new(
+    look_from: Point3,
+    look_at: Point3,
+    view_up: Vec3,
+    vertical_fov: f64,
+    aspect_ratio: f64,
+    aperture_radius: f64,
+    focus_distance: f64,
+  ) -> Self {
+    let viewport_height = 2.0 * (vertical_fov.to_radians() / 2.0).tan();
     let viewport_width = aspect_ratio * viewport_height;
-    let focal_length = 1.0;
 
-    let origin = Point3::default();
-    let horizontal = Vec3::new(viewport_width, 0.0, 0.0);
-    let vertical = Vec3::new(0.0, viewport_height, 0.0);
+    let w = (look_from - look_at).unit();
+    let u = view_up.cross(&w).unit();
+    let v = w.cross(&u);
 
-    let lower_left_corner =
-      origin - (horizontal / 2.0) - (vertical / 2.0) - Vec3::new(0.0, 0.0, focal_length);
+    let origin = look_from;
+    let horizontal = focus_distance * viewport_width * u;
+    let vertical = focus_distance * viewport_height * v;
+
+    let lower_left_corner = origin - (horizontal / 2.0) - (vertical / 2.0) - (focus_distance * w);
 
     Camera {
       origin,
       lower_left_corner,
       horizontal,
       vertical,
+      u,
+      v,
+      lens_radius: aperture_radius,
     }
   }
 
-  pub fn get_ray(&self, (u, v): (f64, f64)) -> Ray {
+  fn lens_offset(&self, sampler: &mut dyn Sampler) -> Vec3 {
+    if self.lens_radius == 0.0 {
+      return Vec3::default();
+    }
+
+    let (x, y, _) = (self.lens_radius * diffuse::random_point_in_unit_disk(sampler)).tuple();
+    (x * self.u) + (y * self.v)
+  }
+
+  pub fn get_ray(&self, (s, t): (f64, f64), sampler: &mut dyn Sampler) -> Ray {
+    let origin = self.origin + self.lens_offset(sampler);
+
     Ray::new(
-      self.origin,
-      self.lower_left_corner + (u * self.horizontal) + (v * self.vertical) - self.origin,
+      origin,
+      self.lower_left_corner + (s * self.horizontal) + (t * self.vertical) - origin,
     )
   }
 }
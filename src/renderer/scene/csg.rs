@@ -0,0 +1,210 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::math::{Mathematical, Range};
+use crate::renderer::core::ray::Ray;
+use crate::renderer::scene::hittable::{Face, Hit, Hittable};
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CsgOperation {
+  Union,
+  Intersection,
+  /// The left solid with the right one cut out of it.
+  Difference,
+}
+
+impl CsgOperation {
+  /// Whether a point inside or outside each of the solids is inside the result.
+  fn contains(self, in_left: bool, in_right: bool) -> bool {
+    match self {
+      CsgOperation::Union => in_left || in_right,
+      CsgOperation::Intersection => in_left && in_right,
+      CsgOperation::Difference => in_left && !in_right,
+    }
+  }
+}
+
+/// A solid combining two others, which must be closed for the ray to be
+/// tracked in and out of them. Each may itself be made of several overlapping
+/// solids. Its surface is made of the crossings of theirs where the ray moves
+/// into or out of the result.
+pub struct Csg {
+  operation: CsgOperation,
+  left: Arc<dyn Hittable>,
+  right: Arc<dyn Hittable>,
+  bounding_box: Option<Aabb>,
+}
+
+impl Csg {
+  pub fn new(operation: CsgOperation, left: Arc<dyn Hittable>, right: Arc<dyn Hittable>) -> Self {
+    let bounding_box = match operation {
+      CsgOperation::Union => left
+        .bounding_box()
+        .zip(right.bounding_box())
+        .map(|(left, right)| left.surrounding(&right)),
+      CsgOperation::Intersection => left.bounding_box().or(right.bounding_box()),
+      CsgOperation::Difference => left.bounding_box(),
+    };
+
+    Self {
+      operation,
+      left,
+      right,
+      bounding_box,
+    }
+  }
+}
+
+impl Hittable for Csg {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    self.hits(ray, hittable_range).into_iter().next()
+  }
+
+  fn hits(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Hit> {
+    if self
+      .bounding_box
+      .is_some_and(|aabb| !aabb.hit(ray, hittable_range))
+    {
+      return vec![];
+    }
+
+    // Whether the ray starts inside either solid depends on the crossings
+    // before the range, so both are followed from as far back as the ray goes.
+    let range = Range::new(f64::NEG_INFINITY, hittable_range.end());
+    let mut left = self.left.hits(ray, range).into_iter().peekable();
+    let mut right = self.right.hits(ray, range).into_iter().peekable();
+
+    // How many of the solids making up each side the ray is inside, as they
+    // may overlap.
+    let (mut left_depth, mut right_depth) = (0, 0);
+    let mut hits = Vec::new();
+
+    loop {
+      let from_left = match (left.peek(), right.peek()) {
+        (Some(left), Some(right)) => left.time() <= right.time(),
+        (Some(_), None) => true,
+        (None, Some(_)) => false,
+        (None, None) => break,
+      };
+      let Some(hit) = (if from_left { left.next() } else { right.next() }) else {
+        break;
+      };
+
+      let was_inside = self.operation.contains(left_depth > 0, right_depth > 0);
+      let step = match hit.face() {
+        Face::Front => 1,
+        Face::Back => -1,
+      };
+      if from_left {
+        left_depth += step;
+      } else {
+        right_depth += step;
+      }
+
+      if (self.operation.contains(left_depth > 0, right_depth > 0) != was_inside)
+        && hit.time().is_within(hittable_range)
+      {
+        // Where the right solid is cut out, its surface faces into the result.
+        hits.push(
+          if !from_left && (self.operation == CsgOperation::Difference) {
+            hit.flipped()
+          } else {
+            hit
+          },
+        );
+      }
+    }
+
+    hits
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    self.bounding_box
+  }
+
+  fn is_solid(&self) -> bool {
+    true
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::renderer::core::transform::Transform;
+  use crate::renderer::core::vec3::{Color, Point3, Vec3};
+  use crate::renderer::materials::lambertian::Lambertian;
+  use crate::renderer::scene::cuboid::Cuboid;
+  use crate::renderer::scene::instance::Instance;
+
+  fn unit_cube() -> Arc<dyn Hittable> {
+    Arc::new(Cuboid::new(
+      Point3::new(0.0, 0.0, 0.0),
+      Point3::new(1.0, 1.0, 1.0),
+      Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),
+    ))
+  }
+
+  fn times(object: &dyn Hittable, ray: &Ray) -> Vec<f64> {
+    object
+      .hits(ray, Range::new(0.001, f64::INFINITY))
+      .iter()
+      .map(Hit::time)
+      .collect()
+  }
+
+  #[test]
+  fn union_with_overlapping_instances_crosses_only_the_outer_surface() {
+    let cube = unit_cube();
+    // Two copies of the cube overlapping over x in [0.5, 1], joined to a third
+    // which is apart from both.
+    let overlapping: Arc<dyn Hittable> = Arc::new(vec![
+      Instance::new(
+        Arc::clone(&cube),
+        Transform::translate(&Vec3::new(0.0, 0.0, 0.0)),
+      ),
+      Instance::new(
+        Arc::clone(&cube),
+        Transform::translate(&Vec3::new(0.5, 0.0, 0.0)),
+      ),
+    ]);
+    let apart = Arc::new(Instance::new(
+      cube,
+      Transform::translate(&Vec3::new(3.0, 0.0, 0.0)),
+    ));
+    let union = Csg::new(CsgOperation::Union, overlapping, apart);
+
+    let ray = Ray::new(Point3::new(-1.0, 0.5, 0.5), Vec3::new(1.0, 0.0, 0.0));
+    let times = times(&union, &ray);
+
+    assert_eq!(times.len(), 4, "{times:?}");
+    for (time, expected) in times.iter().zip([1.0, 2.5, 4.0, 5.0]) {
+      assert!((time - expected).abs() < 1e-9, "{times:?}");
+    }
+  }
+
+  #[test]
+  fn difference_cuts_overlapping_instances_out_together() {
+    let cube = unit_cube();
+    let block = Arc::new(Instance::new(
+      Arc::clone(&cube),
+      Transform::scale(&Vec3::new(4.0, 1.0, 1.0)),
+    ));
+    let holes: Arc<dyn Hittable> = Arc::new(vec![
+      Instance::new(
+        Arc::clone(&cube),
+        Transform::translate(&Vec3::new(1.0, 0.0, 0.0)),
+      ),
+      Instance::new(cube, Transform::translate(&Vec3::new(1.5, 0.0, 0.0))),
+    ]);
+    let difference = Csg::new(CsgOperation::Difference, block, holes);
+
+    let ray = Ray::new(Point3::new(-1.0, 0.5, 0.5), Vec3::new(1.0, 0.0, 0.0));
+    let times = times(&difference, &ray);
+
+    assert_eq!(times.len(), 4, "{times:?}");
+    for (time, expected) in times.iter().zip([1.0, 2.0, 3.5, 5.0]) {
+      assert!((time - expected).abs() < 1e-9, "{times:?}");
+    }
+  }
+}
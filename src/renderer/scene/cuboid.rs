@@ -0,0 +1,109 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::math::Range;
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec3::{Point3, Vec3};
+use crate::renderer::materials::material::Material;
+use crate::renderer::samplers::sampler::Sampler;
+use crate::renderer::scene::hittable::{self, Hit, Hittable};
+use crate::renderer::scene::quad::Quad;
+use std::sync::Arc;
+
+/// An axis-aligned box made of six quads facing outwards. Boxes at other
+/// angles can be made by instancing one.
+pub struct Cuboid {
+  faces: [Quad; 6],
+  bounding_box: Aabb,
+  area: f64,
+}
+
+impl Cuboid {
+  /// Creates the box with opposite corners `a` and `b`.
+  pub fn new(a: Point3, b: Point3, material: Arc<dyn Material>) -> Self {
+    let (min, max) = (a.min(&b), a.max(&b));
+    let extent = max - min;
+    let dx = Vec3::new(extent.x(), 0.0, 0.0);
+    let dy = Vec3::new(0.0, extent.y(), 0.0);
+    let dz = Vec3::new(0.0, 0.0, extent.z());
+
+    let face = |corner: Point3, u: Vec3, v: Vec3| Quad::new(corner, u, v, Arc::clone(&material));
+    let faces = [
+      // Front, right, back and left, with v pointing up.
+      face(Point3::new(min.x(), min.y(), max.z()), dx, dy),
+      face(Point3::new(max.x(), min.y(), max.z()), -dz, dy),
+      face(Point3::new(max.x(), min.y(), min.z()), -dx, dy),
+      face(min, dz, dy),
+      // Top and bottom.
+      face(Point3::new(min.x(), max.y(), max.z()), dx, -dz),
+      face(min, dx, dz),
+    ];
+    let area = faces.iter().map(Quad::area).sum();
+
+    Self {
+      faces,
+      bounding_box: Aabb::new(min, max),
+      area,
+    }
+  }
+}
+
+impl Hittable for Cuboid {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    let mut closest = None;
+    let mut range = hittable_range;
+
+    for face in &self.faces {
+      if let Some(hit) = face.hit(ray, range) {
+        range = Range::new(range.start(), hit.time());
+        closest = Some(hit);
+      }
+    }
+
+    closest
+  }
+
+  fn hits(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Hit> {
+    let mut hits = self
+      .faces
+      .iter()
+      .filter_map(|face| face.hit(ray, hittable_range))
+      .collect::<Vec<_>>();
+
+    hittable::sort_by_time(&mut hits);
+    hits
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    Some(self.bounding_box)
+  }
+
+  fn is_solid(&self) -> bool {
+    true
+  }
+
+  /// Picks a face with probability proportional to its area, then a point on
+  /// it.
+  fn sample_point(&self, origin: &Point3, sampler: &mut dyn Sampler) -> Option<(Point3, f64)> {
+    let mut target = sampler.get_1d() * self.area;
+    let face = self
+      .faces
+      .iter()
+      .find(|face| {
+        target -= face.area();
+        target < 0.0
+      })
+      .unwrap_or(&self.faces[5]);
+
+    let (point, _) = face.sample_point(origin, sampler)?;
+    Some((point, self.pdf(origin, &(point - *origin))))
+  }
+
+  /// The density of every face crossed along `direction`, since any of them
+  /// could have been picked.
+  fn pdf(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    self
+      .faces
+      .iter()
+      .map(|face| (face.area() / self.area) * face.pdf(origin, direction))
+      .sum()
+  }
+}
@@ -0,0 +1,103 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::diffuse;
+use crate::renderer::core::math::{Mathematical, Range};
+use crate::renderer::core::onb::Onb;
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec2::Vec2;
+use crate::renderer::core::vec3::{Point3, Vec3};
+use crate::renderer::materials::material::Material;
+use crate::renderer::samplers::sampler::Sampler;
+use crate::renderer::scene::hittable::{self, Hit, Hittable};
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// A flat disk facing outwards along `normal`. Its surface coordinates are the
+/// angle around the centre, as a fraction of a turn, and the distance from the
+/// centre, as a fraction of the radius.
+pub struct Disk {
+  center: Point3,
+  basis: Onb,
+  radius: f64,
+  material: Arc<dyn Material>,
+}
+
+impl Disk {
+  pub fn new(center: Point3, normal: Vec3, radius: f64, material: Arc<dyn Material>) -> Self {
+    Self {
+      center,
+      basis: Onb::from_w(&normal),
+      radius,
+      material,
+    }
+  }
+
+  fn normal(&self) -> Vec3 {
+    self.basis.local(0.0, 0.0, 1.0)
+  }
+
+  fn area(&self) -> f64 {
+    PI * self.radius.powi(2)
+  }
+
+  /// The time at which `ray` crosses the disk, and the crossing in the disk's
+  /// local coordinates.
+  fn intersect(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<(f64, Vec3)> {
+    let normal = self.normal();
+    let denominator = normal.dot(&ray.direction());
+
+    // Skip rays along the disk, however long their direction.
+    if denominator.abs() <= f64::EPSILON * normal.length() * ray.direction().length() {
+      return None;
+    }
+
+    let time = normal.dot(&(self.center - ray.origin())) / denominator;
+    if !time.is_within(hittable_range) {
+      return None;
+    }
+
+    let local = self.basis.to_local(&(ray.at(time) - self.center));
+    (local.x().hypot(local.y()) <= self.radius).then_some((time, local))
+  }
+}
+
+impl Hittable for Disk {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    self.intersect(ray, hittable_range).map(|(time, local)| {
+      let angle = local.y().atan2(local.x()).rem_euclid(2.0 * PI);
+      let distance = local.x().hypot(local.y());
+
+      Hit::new(time, ray, Arc::clone(&self.material), |_| self.normal())
+        .with_uv(Vec2::new(angle / (2.0 * PI), distance / self.radius))
+    })
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    // How far the rim reaches along each axis.
+    let extent = self
+      .normal()
+      .map(|axis| self.radius * (1.0 - axis.powi(2)).max(0.0).sqrt());
+
+    Some(Aabb::new(self.center - extent, self.center + extent))
+  }
+
+  fn sample_point(&self, origin: &Point3, sampler: &mut dyn Sampler) -> Option<(Point3, f64)> {
+    let (x, y, _) = (self.radius * diffuse::random_point_in_unit_disk(sampler)).tuple();
+    let point = self.center + self.basis.local(x, y, 0.0);
+
+    Some((
+      point,
+      hittable::area_to_solid_angle(1.0 / self.area(), origin, &point, &self.normal()),
+    ))
+  }
+
+  fn pdf(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    let ray = Ray::new(*origin, *direction);
+
+    match self.intersect(&ray, Range::new(0.001, f64::INFINITY)) {
+      Some((time, _)) => {
+        hittable::area_to_solid_angle(1.0 / self.area(), origin, &ray.at(time), &self.normal())
+      }
+      None => 0.0,
+    }
+  }
+}
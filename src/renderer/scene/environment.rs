@@ -0,0 +1,184 @@
+use crate::image::format::LinearImage;
+use crate::renderer::core::color::{
+  self, COLOR_BLUE, COLOR_GREEN, COLOR_LIGHT_BLUE, COLOR_RED, COLOR_WHITE, COLOR_YELLOW,
+};
+use crate::renderer::core::distribution::Distribution2D;
+use crate::renderer::core::math::{self, Range};
+use crate::renderer::core::vec3::{Color, Vec3};
+use crate::renderer::samplers::sampler::Sampler;
+use std::f64::consts::PI;
+
+/// The light arriving from infinitely far away, seen by rays which escape the
+/// scene.
+pub enum Environment {
+  Constant(Color),
+  /// Blends from `bottom` straight down to `top` straight up.
+  Gradient {
+    top: Color,
+    bottom: Color,
+  },
+  Map(EnvironmentMap),
+  /// A different primary colour for each quadrant of directions, to debug
+  /// refraction.
+  Debug,
+}
+
+impl Default for Environment {
+  fn default() -> Self {
+    Environment::Gradient {
+      top: COLOR_LIGHT_BLUE,
+      bottom: COLOR_WHITE,
+    }
+  }
+}
+
+impl Environment {
+  /// The radiance arriving along the opposite of `direction`.
+  pub fn radiance(&self, direction: &Vec3) -> Color {
+    match self {
+      Environment::Constant(color) => *color,
+      Environment::Gradient { top, bottom } => {
+        let direction = direction.unit();
+        let time = math::map_range(direction.y(), Range::new(-1.0, 1.0), Range::new(0.0, 1.0));
+        color::linear_blend(*top, *bottom, time)
+      }
+      Environment::Map(map) => map.radiance(direction),
+      Environment::Debug => match (direction.x() >= 0.0, direction.y() >= 0.0) {
+        (true, true) => COLOR_RED,
+        (true, false) => COLOR_GREEN,
+        (false, true) => COLOR_BLUE,
+        (false, false) => COLOR_YELLOW,
+      },
+    }
+  }
+
+  /// Picks a direction towards the environment in proportion to its brightness,
+  /// returning the direction and its density over solid angle. Environments
+  /// which are not worth sampling directly return `None`.
+  pub fn sample(&self, sampler: &mut dyn Sampler) -> Option<(Vec3, f64)> {
+    match self {
+      Environment::Map(map) => map.sample(sampler),
+      _ => None,
+    }
+  }
+
+  /// The density over solid angle with which `sample` picks `direction`.
+  pub fn pdf(&self, direction: &Vec3) -> f64 {
+    match self {
+      Environment::Map(map) => map.pdf(direction),
+      _ => 0.0,
+    }
+  }
+}
+
+/// An equirectangular (latitude-longitude) image surrounding the scene, with +y
+/// up and the centre of the image facing -z.
+pub struct EnvironmentMap {
+  image: LinearImage,
+  rotation: f64,
+  intensity: f64,
+  distribution: Distribution2D,
+}
+
+impl EnvironmentMap {
+  /// Creates a map turned `rotation_deg` about the vertical axis, with its
+  /// radiance scaled by `intensity`.
+  pub fn new(image: LinearImage, rotation_deg: f64, intensity: f64) -> Self {
+    // Rows near the poles are squeezed onto a smaller solid angle, so weight
+    // each texel's luminance by the sine of its polar angle.
+    let luminance = image
+      .pixels
+      .chunks_exact(image.width)
+      .enumerate()
+      .flat_map(|(y, row)| {
+        let sin_theta = (PI * (y as f64 + 0.5) / image.height as f64).sin();
+        row
+          .iter()
+          .map(move |pixel| color::luminance(*pixel).max(0.0) * sin_theta)
+      })
+      .collect::<Vec<_>>();
+    let distribution = Distribution2D::new(&luminance, image.width);
+
+    Self {
+      image,
+      rotation: rotation_deg.to_radians(),
+      intensity,
+      distribution,
+    }
+  }
+
+  /// The image coordinates in [0, 1]² of `direction`, from the top left.
+  fn direction_to_uv(&self, direction: &Vec3) -> (f64, f64) {
+    let direction = direction.unit();
+    let azimuth = direction.x().atan2(-direction.z()) + self.rotation;
+
+    let u = (0.5 + (azimuth / (2.0 * PI))).rem_euclid(1.0);
+    let v = direction.y().clamp(-1.0, 1.0).acos() / PI;
+
+    (u, v)
+  }
+
+  /// The direction of the image coordinates (u, v), the inverse of
+  /// `direction_to_uv`.
+  fn uv_to_direction(&self, (u, v): (f64, f64)) -> Vec3 {
+    let azimuth = ((u - 0.5) * 2.0 * PI) - self.rotation;
+    let theta = v * PI;
+
+    Vec3::new(
+      theta.sin() * azimuth.sin(),
+      theta.cos(),
+      -theta.sin() * azimuth.cos(),
+    )
+  }
+
+  /// Converts a density over image coordinates to one over solid angle, at
+  /// polar angle `theta`.
+  fn solid_angle_pdf(uv_pdf: f64, theta: f64) -> f64 {
+    let sin_theta = theta.sin();
+
+    if sin_theta <= 0.0 {
+      0.0
+    } else {
+      uv_pdf / (2.0 * PI * PI * sin_theta)
+    }
+  }
+
+  pub fn sample(&self, sampler: &mut dyn Sampler) -> Option<(Vec3, f64)> {
+    let (uv, uv_pdf) = self.distribution.sample(sampler.get_2d());
+    let pdf = Self::solid_angle_pdf(uv_pdf, uv.1 * PI);
+
+    (pdf > 0.0).then(|| (self.uv_to_direction(uv), pdf))
+  }
+
+  pub fn pdf(&self, direction: &Vec3) -> f64 {
+    let uv = self.direction_to_uv(direction);
+    Self::solid_angle_pdf(self.distribution.pdf(uv), uv.1 * PI)
+  }
+
+  fn texel(&self, x: usize, y: usize) -> Color {
+    self.image.pixels[(y * self.image.width) + x]
+  }
+
+  /// Bilinearly filters the image around `direction`, wrapping horizontally.
+  pub fn radiance(&self, direction: &Vec3) -> Color {
+    let (width, height) = (self.image.width, self.image.height);
+    let (u, v) = self.direction_to_uv(direction);
+
+    let x = (u * width as f64) - 0.5;
+    let y = ((v * height as f64) - 0.5).clamp(0.0, (height - 1) as f64);
+
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let (fx, fy) = (x - x0, y - y0);
+
+    let left = (x0 as isize).rem_euclid(width as isize) as usize;
+    let right = (left + 1) % width;
+    let top = y0 as usize;
+    let bottom = (top + 1).min(height - 1);
+
+    let upper = color::linear_blend(self.texel(right, top), self.texel(left, top), fx);
+    let lower = color::linear_blend(self.texel(right, bottom), self.texel(left, bottom), fx);
+
+    self.intensity * color::linear_blend(lower, upper, fy)
+  }
+}
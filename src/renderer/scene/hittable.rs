@@ -1,7 +1,11 @@
+use crate::renderer::core::aabb::Aabb;
 use crate::renderer::core::math::Range;
 use crate::renderer::core::ray::Ray;
+use crate::renderer::core::transform::Transform;
+use crate::renderer::core::vec2::Vec2;
 use crate::renderer::core::vec3::{Point3, Vec3};
 use crate::renderer::materials::material::Material;
+use crate::renderer::samplers::sampler::Sampler;
 use std::sync::Arc;
 
 #[derive(Clone, Copy)]
@@ -14,8 +18,12 @@ pub struct Hit {
   time: f64,
   point: Point3,
   normal: Vec3,
+  /// The normal of the surface itself, unchanged by shading normals.
+  geometric_normal: Vec3,
   face: Face,
   material: Arc<dyn Material>,
+  uv: Vec2<f64>,
+  barycentric: Option<Vec2<f64>>,
 }
 
 impl Hit {
@@ -33,18 +41,73 @@ impl Hit {
       Face::Back
     };
 
+    let normal = match face {
+      Face::Front => outward_normal,
+      Face::Back => -outward_normal,
+    };
+
     Hit {
       time,
       point,
-      normal: match face {
-        Face::Front => outward_normal,
-        Face::Back => -outward_normal,
-      },
+      normal,
+      geometric_normal: normal,
       material,
       face,
+      uv: Vec2::default(),
+      barycentric: None,
     }
   }
 
+  /// Sets the surface coordinates of the hit point, used to look up textures.
+  pub fn with_uv(mut self, uv: Vec2<f64>) -> Self {
+    self.uv = uv;
+    self
+  }
+
+  /// Sets the barycentric coordinates (u, v) of the hit point within a
+  /// triangle, where the weights of the triangle's vertices are (1 - u - v, u,
+  /// v).
+  pub fn with_barycentric(mut self, barycentric: Vec2<f64>) -> Self {
+    self.barycentric = Some(barycentric);
+    self
+  }
+
+  /// Replaces the normal used for shading, e.g. one interpolated across a
+  /// surface, keeping it on the same side as the geometric normal.
+  pub fn with_shading_normal(mut self, normal: Vec3) -> Self {
+    let normal = normal.unit();
+
+    self.normal = if normal.dot(&self.normal) < 0.0 {
+      -normal
+    } else {
+      normal
+    };
+    self
+  }
+
+  /// Turns the surface inside out at the hit, so that the ray leaves the solid
+  /// where it entered it and the other way around.
+  pub fn flipped(mut self) -> Self {
+    self.face = match self.face {
+      Face::Front => Face::Back,
+      Face::Back => Face::Front,
+    };
+    self
+  }
+
+  /// Moves the hit from an object's own space into the space `transform`
+  /// places it in.
+  pub fn transformed(mut self, transform: &Transform) -> Self {
+    self.point = transform.point(&self.point);
+    self.normal = transform.normal(&self.normal).unit();
+    self.geometric_normal = transform.normal(&self.geometric_normal).unit();
+    self
+  }
+
+  pub fn time(&self) -> f64 {
+    self.time
+  }
+
   pub fn point(&self) -> Point3 {
     self.point
   }
@@ -53,6 +116,10 @@ impl Hit {
     self.normal
   }
 
+  pub fn geometric_normal(&self) -> Vec3 {
+    self.geometric_normal
+  }
+
   pub fn face(&self) -> Face {
     self.face
   }
@@ -60,22 +127,131 @@ impl Hit {
   pub fn material(&self) -> &dyn Material {
     self.material.as_ref()
   }
+
+  pub fn uv(&self) -> Vec2<f64> {
+    self.uv
+  }
+
+  pub fn barycentric(&self) -> Option<Vec2<f64>> {
+    self.barycentric
+  }
 }
 
 pub trait Hittable: Send + Sync {
   fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit>;
+
+  /// Every crossing of the surface within `hittable_range`, in order of time.
+  /// Crossings at the front face enter the solid and those at the back face
+  /// leave it. Objects which only report their closest hit cannot be used in
+  /// solid geometry.
+  fn hits(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Hit> {
+    self.hit(ray, hittable_range).into_iter().collect()
+  }
+
+  /// The box enclosing the entire object, or `None` if the object is unbounded.
+  fn bounding_box(&self) -> Option<Aabb>;
+
+  /// Whether the surface closes off a solid, so that `hits` alternately enters
+  /// and leaves it, starting from outside.
+  fn is_solid(&self) -> bool {
+    false
+  }
+
+  /// Picks a point on the surface to sample light from, returning the point and
+  /// the density of the direction towards it from `origin`, over solid angle.
+  /// Objects which cannot be sampled return `None`.
+  fn sample_point(&self, _origin: &Point3, _sampler: &mut dyn Sampler) -> Option<(Point3, f64)> {
+    None
+  }
+
+  /// The density over solid angle with which `sample_point` picks `direction`
+  /// from `origin`.
+  fn pdf(&self, _origin: &Point3, _direction: &Vec3) -> f64 {
+    0.0
+  }
+}
+
+/// Orders `hits` by time.
+pub fn sort_by_time(hits: &mut [Hit]) {
+  hits.sort_by(|first, second| first.time().total_cmp(&second.time()));
+}
+
+/// Converts a density over the area of a surface at `point`, facing `normal`,
+/// to a density over solid angle seen from `origin`.
+pub fn area_to_solid_angle(area_pdf: f64, origin: &Point3, point: &Point3, normal: &Vec3) -> f64 {
+  let to_point = *point - *origin;
+  let cosine = normal.unit().dot(&to_point.unit()).abs();
+
+  if cosine > 0.0 {
+    area_pdf * to_point.length_squared() / cosine
+  } else {
+    0.0
+  }
+}
+
+impl Hittable for Arc<dyn Hittable> {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    self.as_ref().hit(ray, hittable_range)
+  }
+
+  fn hits(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Hit> {
+    self.as_ref().hits(ray, hittable_range)
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    self.as_ref().bounding_box()
+  }
+
+  fn is_solid(&self) -> bool {
+    self.as_ref().is_solid()
+  }
+
+  fn sample_point(&self, origin: &Point3, sampler: &mut dyn Sampler) -> Option<(Point3, f64)> {
+    self.as_ref().sample_point(origin, sampler)
+  }
+
+  fn pdf(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    self.as_ref().pdf(origin, direction)
+  }
 }
 
 impl<T: Hittable> Hittable for Vec<T> {
   fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
-    let mut vec = self
+    let mut closest = None;
+    let mut range = hittable_range;
+
+    for value in self {
+      if let Some(hit) = value.hit(ray, range) {
+        range = Range::new(range.start(), hit.time());
+        closest = Some(hit);
+      }
+    }
+
+    closest
+  }
+
+  fn hits(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Hit> {
+    let mut hits = self
       .iter()
-      .map(|value| value.hit(ray, hittable_range))
-      .filter(|value| value.is_some())
-      .flatten()
+      .flat_map(|value| value.hits(ray, hittable_range))
       .collect::<Vec<_>>();
-    vec.sort_unstable_by(|a, b| b.time.total_cmp(&a.time));
 
-    vec.pop()
+    sort_by_time(&mut hits);
+    hits
+  }
+
+  fn is_solid(&self) -> bool {
+    self.iter().all(Hittable::is_solid)
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    self.iter().try_fold(None, |aabb: Option<Aabb>, value| {
+      let value_aabb = value.bounding_box()?;
+
+      Some(Some(match aabb {
+        Some(aabb) => aabb.surrounding(&value_aabb),
+        None => value_aabb,
+      }))
+    })?
   }
 }
@@ -0,0 +1,86 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::math::Range;
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::transform::Transform;
+use crate::renderer::core::vec3::{Point3, Vec3};
+use crate::renderer::samplers::sampler::Sampler;
+use crate::renderer::scene::hittable::{Hit, Hittable};
+use std::sync::Arc;
+
+/// An object placed in the scene by a transform. Instances share their object,
+/// so a heavy mesh can be placed many times over for the cost of one.
+pub struct Instance {
+  object: Arc<dyn Hittable>,
+  /// From the object's own space into the scene.
+  transform: Transform,
+  bounding_box: Option<Aabb>,
+}
+
+impl Instance {
+  pub fn new(object: Arc<dyn Hittable>, transform: Transform) -> Self {
+    let bounding_box = object
+      .bounding_box()
+      .map(|aabb| transform.bounding_box(&aabb));
+
+    Self {
+      object,
+      transform,
+      bounding_box,
+    }
+  }
+}
+
+impl Hittable for Instance {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    // The object space ray is not unit length, so that hit times match.
+    let local_ray = self.transform.inverse().ray(ray);
+
+    self
+      .object
+      .hit(&local_ray, hittable_range)
+      .map(|hit| hit.transformed(&self.transform))
+  }
+
+  fn hits(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Hit> {
+    let local_ray = self.transform.inverse().ray(ray);
+
+    self
+      .object
+      .hits(&local_ray, hittable_range)
+      .into_iter()
+      .map(|hit| hit.transformed(&self.transform))
+      .collect()
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    self.bounding_box
+  }
+
+  fn is_solid(&self) -> bool {
+    self.object.is_solid()
+  }
+
+  // Densities over solid angle only carry over between spaces when the
+  // transform keeps angles, so other instances are not sampled as lights.
+  fn sample_point(&self, origin: &Point3, sampler: &mut dyn Sampler) -> Option<(Point3, f64)> {
+    if !self.transform.is_similarity() {
+      return None;
+    }
+
+    let local_origin = self.transform.inverse().point(origin);
+    let (point, pdf) = self.object.sample_point(&local_origin, sampler)?;
+
+    Some((self.transform.point(&point), pdf))
+  }
+
+  fn pdf(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    if !self.transform.is_similarity() {
+      return 0.0;
+    }
+
+    let inverse = self.transform.inverse();
+    self
+      .object
+      .pdf(&inverse.point(origin), &inverse.vector(direction).unit())
+  }
+}
@@ -0,0 +1,246 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::distribution::Distribution1D;
+use crate::renderer::core::math::Range;
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec2::Vec2;
+use crate::renderer::core::vec3::{Point3, Vec3};
+use crate::renderer::materials::material::Material;
+use crate::renderer::samplers::sampler::Sampler;
+use crate::renderer::scene::bvh::Bvh;
+use crate::renderer::scene::hittable::{self, Hit, Hittable};
+use crate::renderer::scene::triangle;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Vertex attributes shared between the faces of a mesh.
+#[derive(Default)]
+pub struct MeshBuffers {
+  pub positions: Vec<Point3>,
+  pub normals: Vec<Vec3>,
+  pub uvs: Vec<Vec2<f64>>,
+}
+
+/// A triangle of a mesh, given as indices into each of the mesh's buffers.
+/// Faces without normals are flat shaded.
+#[derive(Clone, Copy)]
+pub struct MeshFace {
+  pub positions: [usize; 3],
+  pub normals: Option<[usize; 3]>,
+  pub uvs: Option<[usize; 3]>,
+}
+
+struct MeshData {
+  buffers: Arc<MeshBuffers>,
+  faces: Vec<MeshFace>,
+  material: Arc<dyn Material>,
+}
+
+impl MeshData {
+  fn positions(&self, face: &MeshFace) -> [Point3; 3] {
+    face.positions.map(|index| self.buffers.positions[index])
+  }
+}
+
+struct MeshTriangle {
+  mesh: Arc<MeshData>,
+  face: usize,
+}
+
+fn interpolate(values: [Vec3; 3], barycentric: Vec2<f64>) -> Vec3 {
+  let (u, v) = (barycentric.x(), barycentric.y());
+
+  ((1.0 - u - v) * values[0]) + (u * values[1]) + (v * values[2])
+}
+
+impl Hittable for MeshTriangle {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    let face = &self.mesh.faces[self.face];
+    let [p0, p1, p2] = self.mesh.positions(face);
+
+    let (time, barycentric) = triangle::intersect(ray, [p0, p1, p2], hittable_range)?;
+
+    let hit = Hit::new(time, ray, Arc::clone(&self.mesh.material), |_| {
+      triangle::normal([p0, p1, p2])
+    })
+    .with_barycentric(barycentric);
+
+    let hit = match face.normals {
+      Some(normals) => hit.with_shading_normal(interpolate(
+        normals.map(|index| self.mesh.buffers.normals[index]),
+        barycentric,
+      )),
+      None => hit,
+    };
+
+    let uv = match face.uvs {
+      Some(uvs) => {
+        let uv = interpolate(
+          uvs.map(|index| {
+            let uv = self.mesh.buffers.uvs[index];
+            Vec3::new(uv.x(), uv.y(), 0.0)
+          }),
+          barycentric,
+        );
+
+        Vec2::new(uv.x(), uv.y())
+      }
+      None => barycentric,
+    };
+
+    Some(hit.with_uv(uv))
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    Some(Aabb::from_points(
+      &self.mesh.positions(&self.mesh.faces[self.face]),
+    ))
+  }
+}
+
+/// An indexed triangle mesh sharing its vertex buffers between all faces, with
+/// its own acceleration structure. The buffers may also be shared with other
+/// meshes.
+pub struct TriangleMesh {
+  mesh: Arc<MeshData>,
+  bvh: Bvh,
+  /// Picks faces in proportion to their area, for sampling light from the mesh.
+  face_areas: Distribution1D,
+  area: f64,
+  solid: bool,
+}
+
+/// Whether `faces` close off a solid: every edge between their vertices is
+/// shared by exactly two faces, which run along it in opposite directions.
+pub fn is_closed<'a>(faces: impl IntoIterator<Item = &'a MeshFace>) -> bool {
+  // The number of faces along each edge, and how many more run one way than
+  // the other.
+  let mut edges: HashMap<(usize, usize), (usize, isize)> = HashMap::new();
+
+  for face in faces {
+    let [a, b, c] = face.positions;
+
+    for (from, to) in [(a, b), (b, c), (c, a)] {
+      let (count, balance) = edges.entry((from.min(to), from.max(to))).or_default();
+      *count += 1;
+      *balance += if from < to { 1 } else { -1 };
+    }
+  }
+
+  !edges.is_empty()
+    && edges
+      .values()
+      .all(|&(count, balance)| (count == 2) && (balance == 0))
+}
+
+impl TriangleMesh {
+  pub fn new(buffers: Arc<MeshBuffers>, faces: Vec<MeshFace>, material: Arc<dyn Material>) -> Self {
+    debug_assert!(
+      faces.iter().all(|face| {
+        face
+          .positions
+          .iter()
+          .all(|&index| index < buffers.positions.len())
+          && face
+            .normals
+            .is_none_or(|normals| normals.iter().all(|&index| index < buffers.normals.len()))
+          && face
+            .uvs
+            .is_none_or(|uvs| uvs.iter().all(|&index| index < buffers.uvs.len()))
+      }),
+      "Mesh faces must only index into the mesh's buffers."
+    );
+
+    let face_count = faces.len();
+    let mesh = Arc::new(MeshData {
+      buffers,
+      faces,
+      material,
+    });
+
+    let face_areas = mesh
+      .faces
+      .iter()
+      .map(|face| triangle::area(mesh.positions(face)))
+      .collect::<Vec<_>>();
+    let area = face_areas.iter().sum();
+
+    Self {
+      face_areas: Distribution1D::new(face_areas),
+      area,
+      solid: false,
+      mesh: Arc::clone(&mesh),
+      bvh: Bvh::new(
+        (0..face_count)
+          .map(|face| {
+            Arc::new(MeshTriangle {
+              mesh: Arc::clone(&mesh),
+              face,
+            }) as Arc<dyn Hittable>
+          })
+          .collect(),
+      ),
+    }
+  }
+
+  /// Marks the mesh as part of a model whose faces together close off a solid.
+  pub fn with_solid(mut self, solid: bool) -> Self {
+    self.solid = solid;
+    self
+  }
+
+  pub fn material(&self) -> &dyn Material {
+    self.mesh.material.as_ref()
+  }
+}
+
+impl Hittable for TriangleMesh {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    self.bvh.hit(ray, hittable_range)
+  }
+
+  fn hits(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Hit> {
+    self.bvh.hits(ray, hittable_range)
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    self.bvh.bounding_box()
+  }
+
+  fn is_solid(&self) -> bool {
+    self.solid
+  }
+
+  fn sample_point(&self, origin: &Point3, sampler: &mut dyn Sampler) -> Option<(Point3, f64)> {
+    if self.area <= 0.0 {
+      return None;
+    }
+
+    let (_, _, face) = self.face_areas.sample(sampler.get_1d());
+    let point = triangle::sample(self.mesh.positions(&self.mesh.faces[face]), sampler);
+
+    Some((point, self.pdf(origin, &(point - *origin))))
+  }
+
+  fn pdf(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    if self.area <= 0.0 {
+      return 0.0;
+    }
+
+    // Any of the faces crossed along `direction` could have been picked.
+    self
+      .hits(
+        &Ray::new(*origin, *direction),
+        Range::new(0.001, f64::INFINITY),
+      )
+      .iter()
+      .map(|hit| {
+        hittable::area_to_solid_angle(
+          1.0 / self.area,
+          origin,
+          &hit.point(),
+          &hit.geometric_normal(),
+        )
+      })
+      .sum()
+  }
+}
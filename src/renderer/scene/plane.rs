@@ -0,0 +1,61 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::math::{Mathematical, Range};
+use crate::renderer::core::onb::Onb;
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec2::Vec2;
+use crate::renderer::core::vec3::{Point3, Vec3};
+use crate::renderer::materials::material::Material;
+use crate::renderer::scene::hittable::{Hit, Hittable};
+use std::sync::Arc;
+
+/// An infinite plane through `point`, facing along `normal`. Its surface
+/// coordinates repeat every `tile_size` along two directions in the plane, for
+/// tiling textures across it.
+pub struct Plane {
+  point: Point3,
+  basis: Onb,
+  tile_size: f64,
+  material: Arc<dyn Material>,
+}
+
+impl Plane {
+  pub fn new(point: Point3, normal: Vec3, tile_size: f64, material: Arc<dyn Material>) -> Self {
+    Self {
+      point,
+      basis: Onb::from_w(&normal),
+      tile_size,
+      material,
+    }
+  }
+
+  fn normal(&self) -> Vec3 {
+    self.basis.local(0.0, 0.0, 1.0)
+  }
+}
+
+impl Hittable for Plane {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    let normal = self.normal();
+    let denominator = normal.dot(&ray.direction());
+
+    // Relative to the lengths, so that short ray directions still hit.
+    if denominator.abs() <= f64::EPSILON * normal.length() * ray.direction().length() {
+      return None;
+    }
+
+    let time = normal.dot(&(self.point - ray.origin())) / denominator;
+    if !time.is_within(hittable_range) {
+      return None;
+    }
+
+    let local = self.basis.to_local(&(ray.at(time) - self.point)) / self.tile_size;
+    let uv = Vec2::new(local.x().rem_euclid(1.0), local.y().rem_euclid(1.0));
+
+    Some(Hit::new(time, ray, Arc::clone(&self.material), |_| normal).with_uv(uv))
+  }
+
+  /// Planes are unbounded, so acceleration structures test them separately.
+  fn bounding_box(&self) -> Option<Aabb> {
+    None
+  }
+}
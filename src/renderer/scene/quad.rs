@@ -0,0 +1,105 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::math::{Mathematical, Range};
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec2::Vec2;
+use crate::renderer::core::vec3::{Point3, Vec3};
+use crate::renderer::materials::material::Material;
+use crate::renderer::samplers::sampler::Sampler;
+use crate::renderer::scene::hittable::{self, Hit, Hittable};
+use std::sync::Arc;
+
+/// A parallelogram spanning the edges `u` and `v` from `corner`. It faces
+/// outwards along `u` × `v`, and its surface coordinates run from 0 to 1
+/// along each edge.
+pub struct Quad {
+  corner: Point3,
+  u: Vec3,
+  v: Vec3,
+  normal: Vec3,
+  /// `u` × `v` divided by its squared length, which projects points in the
+  /// plane onto the edges.
+  w: Vec3,
+  area: f64,
+  material: Arc<dyn Material>,
+}
+
+impl Quad {
+  pub fn new(corner: Point3, u: Vec3, v: Vec3, material: Arc<dyn Material>) -> Self {
+    let cross = u.cross(&v);
+
+    Self {
+      corner,
+      u,
+      v,
+      normal: cross.unit(),
+      w: cross / cross.length_squared(),
+      area: cross.length(),
+      material,
+    }
+  }
+
+  pub fn area(&self) -> f64 {
+    self.area
+  }
+
+  /// The time at which `ray` crosses the quad, and where on it.
+  fn intersect(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<(f64, Vec2<f64>)> {
+    let denominator = self.normal.dot(&ray.direction());
+
+    // The denominator scales with the ray's direction, so whether the ray runs
+    // along the quad is judged relative to its length.
+    if denominator.abs() <= f64::EPSILON * self.normal.length() * ray.direction().length() {
+      return None;
+    }
+
+    let time = self.normal.dot(&(self.corner - ray.origin())) / denominator;
+    if !time.is_within(hittable_range) {
+      return None;
+    }
+
+    let planar = ray.at(time) - self.corner;
+    let alpha = self.w.dot(&planar.cross(&self.v));
+    let beta = self.w.dot(&self.u.cross(&planar));
+
+    let unit = Range::new(0.0, 1.0);
+    (alpha.is_within(unit) && beta.is_within(unit)).then(|| (time, Vec2::new(alpha, beta)))
+  }
+}
+
+impl Hittable for Quad {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    self.intersect(ray, hittable_range).map(|(time, uv)| {
+      Hit::new(time, ray, Arc::clone(&self.material), |_| self.normal).with_uv(uv)
+    })
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    Some(Aabb::from_points(&[
+      self.corner,
+      self.corner + self.u,
+      self.corner + self.v,
+      self.corner + self.u + self.v,
+    ]))
+  }
+
+  fn sample_point(&self, origin: &Point3, sampler: &mut dyn Sampler) -> Option<(Point3, f64)> {
+    let (s, t) = sampler.get_2d();
+    let point = self.corner + (s * self.u) + (t * self.v);
+
+    Some((
+      point,
+      hittable::area_to_solid_angle(1.0 / self.area, origin, &point, &self.normal),
+    ))
+  }
+
+  fn pdf(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    let ray = Ray::new(*origin, *direction);
+
+    match self.intersect(&ray, Range::new(0.001, f64::INFINITY)) {
+      Some((time, _)) => {
+        hittable::area_to_solid_angle(1.0 / self.area, origin, &ray.at(time), &self.normal)
+      }
+      None => 0.0,
+    }
+  }
+}
@@ -0,0 +1,353 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::math::{Mathematical, Range};
+use crate::renderer::core::quadratic::Quadratic;
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec2::Vec2;
+use crate::renderer::core::vec3::{Point3, Vec3};
+use crate::renderer::materials::material::Material;
+use crate::renderer::scene::hittable::{self, Hit, Hittable};
+use serde::Deserialize;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// The coefficients of f(x, y, z) = xx x^2 + yy y^2 + zz z^2 + xy x y + xz x z
+/// + yz y z + x x + y y + z z + constant.
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuadricCoefficients {
+  pub xx: f64,
+  pub yy: f64,
+  pub zz: f64,
+  pub xy: f64,
+  pub xz: f64,
+  pub yz: f64,
+  pub x: f64,
+  pub y: f64,
+  pub z: f64,
+  pub constant: f64,
+}
+
+impl QuadricCoefficients {
+  fn value(&self, point: &Point3) -> f64 {
+    let (x, y, z) = point.tuple();
+
+    (self.xx * x * x)
+      + (self.yy * y * y)
+      + (self.zz * z * z)
+      + (self.xy * x * y)
+      + (self.xz * x * z)
+      + (self.yz * y * z)
+      + (self.x * x)
+      + (self.y * y)
+      + (self.z * z)
+      + self.constant
+  }
+
+  fn gradient(&self, point: &Point3) -> Vec3 {
+    let (x, y, z) = point.tuple();
+
+    Vec3::new(
+      (2.0 * self.xx * x) + (self.xy * y) + (self.xz * z) + self.x,
+      (2.0 * self.yy * y) + (self.xy * x) + (self.yz * z) + self.y,
+      (2.0 * self.zz * z) + (self.xz * x) + (self.yz * y) + self.z,
+    )
+  }
+
+  /// The value of f along `ray`, as a quadratic in time.
+  fn along(&self, ray: &Ray) -> Quadratic {
+    let (ox, oy, oz) = ray.origin().tuple();
+    let (dx, dy, dz) = ray.direction().tuple();
+
+    Quadratic::new(
+      (self.xx * dx * dx)
+        + (self.yy * dy * dy)
+        + (self.zz * dz * dz)
+        + (self.xy * dx * dy)
+        + (self.xz * dx * dz)
+        + (self.yz * dy * dz),
+      (2.0 * ((self.xx * ox * dx) + (self.yy * oy * dy) + (self.zz * oz * dz)))
+        + (self.xy * ((ox * dy) + (oy * dx)))
+        + (self.xz * ((ox * dz) + (oz * dx)))
+        + (self.yz * ((oy * dz) + (oz * dy)))
+        + (self.x * dx)
+        + (self.y * dy)
+        + (self.z * dz),
+      self.value(&ray.origin()),
+    )
+  }
+
+  /// The coefficients of the same surface moved by `offset`.
+  fn translated(&self, offset: &Vec3) -> Self {
+    let (x, y, z) = offset.tuple();
+
+    Self {
+      x: self.x - (2.0 * self.xx * x) - (self.xy * y) - (self.xz * z),
+      y: self.y - (2.0 * self.yy * y) - (self.xy * x) - (self.yz * z),
+      z: self.z - (2.0 * self.zz * z) - (self.xz * x) - (self.yz * y),
+      constant: self.value(&-*offset),
+      ..*self
+    }
+  }
+}
+
+/// Where a ray crosses the surface: the time, and the outward normal and
+/// surface coordinates there.
+type Crossing = (f64, Vec3, Vec2<f64>);
+
+fn axis_vector(axis: usize, length: f64) -> Vec3 {
+  match axis {
+    0 => Vec3::new(length, 0.0, 0.0),
+    1 => Vec3::new(0.0, length, 0.0),
+    _ => Vec3::new(0.0, 0.0, length),
+  }
+}
+
+/// The surface f(x, y, z) = 0 of a quadric, cut to the part within `bounds`.
+/// Points where f <= 0 are inside the solid. If `capped`, the faces of `bounds`
+/// close the solid off where they cut through it. Its surface coordinates are
+/// the angle around the Y axis through the centre of `bounds`, as a fraction of
+/// a turn, and the height as a fraction of the bounds, and on the caps, the
+/// position across the face.
+pub struct Quadric {
+  coefficients: QuadricCoefficients,
+  bounds: Aabb,
+  capped: bool,
+  material: Arc<dyn Material>,
+}
+
+impl Quadric {
+  pub fn new(
+    coefficients: QuadricCoefficients,
+    bounds: Aabb,
+    capped: bool,
+    material: Arc<dyn Material>,
+  ) -> Self {
+    Self {
+      coefficients,
+      bounds,
+      capped,
+      material,
+    }
+  }
+
+  /// Builds the quadric with `coefficients` around the origin, rising from
+  /// `base` to `height` above it and at most `radius` from its axis.
+  fn upright(
+    coefficients: QuadricCoefficients,
+    base: Point3,
+    radius: f64,
+    height: f64,
+    capped: bool,
+    material: Arc<dyn Material>,
+  ) -> Self {
+    Self::new(
+      coefficients.translated(&base),
+      Aabb::new(
+        base - Vec3::new(radius, 0.0, radius),
+        base + Vec3::new(radius, height, radius),
+      ),
+      capped,
+      material,
+    )
+  }
+
+  /// A cylinder standing on `base`.
+  pub fn cylinder(
+    base: Point3,
+    radius: f64,
+    height: f64,
+    capped: bool,
+    material: Arc<dyn Material>,
+  ) -> Self {
+    let coefficients = QuadricCoefficients {
+      xx: 1.0,
+      zz: 1.0,
+      constant: -radius.powi(2),
+      ..Default::default()
+    };
+
+    Self::upright(coefficients, base, radius, height, capped, material)
+  }
+
+  /// A cone standing on its base at `base`, with its tip `height` above it.
+  pub fn cone(
+    base: Point3,
+    radius: f64,
+    height: f64,
+    capped: bool,
+    material: Arc<dyn Material>,
+  ) -> Self {
+    // x^2 + z^2 = (slope (height - y))^2
+    let slope_squared = (radius / height).powi(2);
+    let coefficients = QuadricCoefficients {
+      xx: 1.0,
+      zz: 1.0,
+      yy: -slope_squared,
+      y: 2.0 * slope_squared * height,
+      constant: -slope_squared * height.powi(2),
+      ..Default::default()
+    };
+
+    Self::upright(coefficients, base, radius, height, capped, material)
+  }
+
+  /// A bowl with its lowest point at `base`, `radius` wide at its rim.
+  pub fn paraboloid(
+    base: Point3,
+    radius: f64,
+    height: f64,
+    capped: bool,
+    material: Arc<dyn Material>,
+  ) -> Self {
+    let coefficients = QuadricCoefficients {
+      xx: 1.0,
+      zz: 1.0,
+      y: -radius.powi(2) / height,
+      ..Default::default()
+    };
+
+    Self::upright(coefficients, base, radius, height, capped, material)
+  }
+
+  /// A hyperboloid of one sheet standing on `base`, `radius` wide at its ends
+  /// and `waist_radius` wide halfway up. A waist wider than the ends bulges
+  /// like a barrel instead.
+  pub fn hyperboloid(
+    base: Point3,
+    radius: f64,
+    waist_radius: f64,
+    height: f64,
+    capped: bool,
+    material: Arc<dyn Material>,
+  ) -> Self {
+    // x^2 + z^2 = waist^2 + flare (y - height / 2)^2
+    let half_height = height / 2.0;
+    let flare = (radius.powi(2) - waist_radius.powi(2)) / half_height.powi(2);
+    let coefficients = QuadricCoefficients {
+      xx: 1.0,
+      zz: 1.0,
+      yy: -flare,
+      y: 2.0 * flare * half_height,
+      constant: -waist_radius.powi(2) - (flare * half_height.powi(2)),
+      ..Default::default()
+    };
+
+    Self::upright(
+      coefficients,
+      base,
+      radius.max(waist_radius),
+      height,
+      capped,
+      material,
+    )
+  }
+
+  fn contains(&self, point: &Point3, skipped_axis: Option<usize>) -> bool {
+    (0..3)
+      .filter(|axis| Some(*axis) != skipped_axis)
+      .all(|axis| {
+        point.axis(axis).is_within(Range::new(
+          self.bounds.min().axis(axis),
+          self.bounds.max().axis(axis),
+        ))
+      })
+  }
+
+  /// The crossings of the curved surface within the bounds.
+  fn surface_crossings(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Crossing> {
+    self
+      .coefficients
+      .along(ray)
+      .find_real_roots()
+      .into_iter()
+      .filter(|root| root.is_within(hittable_range) && self.contains(&ray.at(*root), None))
+      .map(|time| {
+        let point = ray.at(time);
+        let local = point - self.bounds.centroid();
+        let angle = local.z().atan2(local.x()).rem_euclid(2.0 * PI);
+        let height = (point.y() - self.bounds.min().y()) / self.bounds.extent().y();
+
+        (
+          time,
+          self.coefficients.gradient(&point),
+          Vec2::new(angle / (2.0 * PI), height),
+        )
+      })
+      .collect()
+  }
+
+  /// The crossings of the faces of the bounds inside the solid.
+  fn cap_crossings(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Crossing> {
+    let mut crossings = Vec::new();
+    if !self.capped {
+      return crossings;
+    }
+
+    for axis in 0..3 {
+      let direction = ray.direction().axis(axis);
+      if direction == 0.0 {
+        continue;
+      }
+
+      for (side, outward) in [(self.bounds.min(), -1.0), (self.bounds.max(), 1.0)] {
+        let time = (side.axis(axis) - ray.origin().axis(axis)) / direction;
+        if !time.is_within(hittable_range) {
+          continue;
+        }
+
+        let point = ray.at(time);
+        if !self.contains(&point, Some(axis)) || (self.coefficients.value(&point) > 0.0) {
+          continue;
+        }
+
+        let across = |axis: usize| {
+          (point.axis(axis) - self.bounds.min().axis(axis)) / self.bounds.extent().axis(axis)
+        };
+        let uv = Vec2::new(across((axis + 1) % 3), across((axis + 2) % 3));
+
+        crossings.push((time, axis_vector(axis, outward), uv));
+      }
+    }
+
+    crossings
+  }
+
+  fn crossings(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Crossing> {
+    let mut crossings = self.cap_crossings(ray, hittable_range);
+    crossings.extend(self.surface_crossings(ray, hittable_range));
+    crossings
+  }
+
+  fn hit_at(&self, ray: &Ray, (time, normal, uv): Crossing) -> Hit {
+    Hit::new(time, ray, Arc::clone(&self.material), |_| normal).with_uv(uv)
+  }
+}
+
+impl Hittable for Quadric {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    self
+      .crossings(ray, hittable_range)
+      .into_iter()
+      .min_by(|first, second| first.0.total_cmp(&second.0))
+      .map(|crossing| self.hit_at(ray, crossing))
+  }
+
+  fn hits(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Hit> {
+    let mut hits = self
+      .crossings(ray, hittable_range)
+      .into_iter()
+      .map(|crossing| self.hit_at(ray, crossing))
+      .collect::<Vec<_>>();
+
+    hittable::sort_by_time(&mut hits);
+    hits
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    Some(self.bounds)
+  }
+
+  fn is_solid(&self) -> bool {
+    self.capped
+  }
+}
@@ -1,9 +1,14 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::diffuse;
 use crate::renderer::core::math::{Mathematical, Range};
+use crate::renderer::core::onb::Onb;
 use crate::renderer::core::quadratic::Quadratic;
 use crate::renderer::core::ray::Ray;
-use crate::renderer::core::vec3::Vec3;
+use crate::renderer::core::vec3::{Point3, Vec3};
 use crate::renderer::materials::material::Material;
-use crate::renderer::scene::hittable::{Hit, Hittable};
+use crate::renderer::samplers::sampler::Sampler;
+use crate::renderer::scene::hittable::{self, Hit, Hittable};
+use std::f64::consts::PI;
 use std::sync::Arc;
 
 pub struct Sphere {
@@ -28,10 +33,22 @@ impl Sphere {
   pub fn radius(&self) -> f64 {
     self.radius
   }
-}
 
-impl Hittable for Sphere {
-  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+  /// The cosine of the half-angle of the cone which the sphere fills when seen
+  /// from `origin`, or `None` if `origin` is inside the sphere.
+  fn cone_cos_theta_max(&self, origin: &Point3) -> Option<f64> {
+    let distance_squared = (self.center() - *origin).length_squared();
+    let radius_squared = self.radius().powi(2);
+
+    (distance_squared > radius_squared).then(|| (1.0 - (radius_squared / distance_squared)).sqrt())
+  }
+
+  fn area(&self) -> f64 {
+    4.0 * PI * self.radius().powi(2)
+  }
+
+  /// The times at which `ray` crosses the sphere, in order.
+  fn roots(&self, ray: &Ray) -> Vec<f64> {
     let ray_to_sphere = ray.origin() - self.center();
 
     Quadratic::new(
@@ -40,12 +57,93 @@ impl Hittable for Sphere {
       ray_to_sphere.length_squared() - self.radius().powi(2),
     )
     .find_real_roots()
-    .into_iter()
-    .find(|root| root.is_within(hittable_range))
-    .map(|root| {
-      Hit::new(root, ray, Arc::clone(&self.material), |point| {
-        point - self.center()
-      })
+  }
+
+  fn hit_at(&self, ray: &Ray, time: f64) -> Hit {
+    Hit::new(time, ray, Arc::clone(&self.material), |point| {
+      point - self.center()
     })
   }
 }
+
+impl Hittable for Sphere {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    self
+      .roots(ray)
+      .into_iter()
+      .find(|root| root.is_within(hittable_range))
+      .map(|root| self.hit_at(ray, root))
+  }
+
+  fn hits(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Hit> {
+    self
+      .roots(ray)
+      .into_iter()
+      .filter(|root| root.is_within(hittable_range))
+      .map(|root| self.hit_at(ray, root))
+      .collect()
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    let extent = Vec3::from(self.radius().abs());
+
+    Some(Aabb::new(self.center() - extent, self.center() + extent))
+  }
+
+  fn is_solid(&self) -> bool {
+    true
+  }
+
+  /// Samples the cone of directions towards the sphere from outside it, or the
+  /// whole surface from inside it.
+  fn sample_point(&self, origin: &Point3, sampler: &mut dyn Sampler) -> Option<(Point3, f64)> {
+    let to_center = self.center() - *origin;
+
+    match self.cone_cos_theta_max(origin) {
+      Some(cos_theta_max) => {
+        let (u, v) = sampler.get_2d();
+        let cos_theta = 1.0 - (u * (1.0 - cos_theta_max));
+        let sin_theta = (1.0 - cos_theta.powi(2)).max(0.0).sqrt();
+        let phi = 2.0 * PI * v;
+
+        let direction =
+          Onb::from_w(&to_center).local(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta);
+
+        // The distance to the near side of the sphere along `direction`.
+        let distance = to_center.length();
+        let distance = (distance * cos_theta)
+          - (self.radius().powi(2) - (distance * sin_theta).powi(2))
+            .max(0.0)
+            .sqrt();
+
+        Some((
+          *origin + (distance * direction),
+          1.0 / (2.0 * PI * (1.0 - cos_theta_max)),
+        ))
+      }
+      None => {
+        let normal = diffuse::random_point_on_unit_sphere(sampler);
+        let point = self.center() + (self.radius().abs() * normal);
+
+        Some((
+          point,
+          hittable::area_to_solid_angle(1.0 / self.area(), origin, &point, &normal),
+        ))
+      }
+    }
+  }
+
+  fn pdf(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    let Some(hit) = self.hit(
+      &Ray::new(*origin, *direction),
+      Range::new(0.001, f64::INFINITY),
+    ) else {
+      return 0.0;
+    };
+
+    match self.cone_cos_theta_max(origin) {
+      Some(cos_theta_max) => 1.0 / (2.0 * PI * (1.0 - cos_theta_max)),
+      None => hittable::area_to_solid_angle(1.0 / self.area(), origin, &hit.point(), &hit.normal()),
+    }
+  }
+}
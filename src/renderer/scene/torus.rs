@@ -0,0 +1,116 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::math::{Mathematical, Range};
+use crate::renderer::core::quartic::Quartic;
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec2::Vec2;
+use crate::renderer::core::vec3::{Point3, Vec3};
+use crate::renderer::materials::material::Material;
+use crate::renderer::scene::hittable::{Hit, Hittable};
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// A ring around the Y axis through `center`, with a tube `minor_radius` thick
+/// running `major_radius` from the axis. Its surface coordinates are the angles
+/// around the axis and around the tube, as fractions of a turn.
+pub struct Torus {
+  center: Point3,
+  major_radius: f64,
+  minor_radius: f64,
+  material: Arc<dyn Material>,
+}
+
+impl Torus {
+  pub fn new(
+    center: Point3,
+    major_radius: f64,
+    minor_radius: f64,
+    material: Arc<dyn Material>,
+  ) -> Self {
+    Self {
+      center,
+      major_radius,
+      minor_radius,
+      material,
+    }
+  }
+
+  /// The times at which `ray` crosses the torus, in order.
+  fn roots(&self, ray: &Ray) -> Vec<f64> {
+    let direction = ray.direction();
+    let length_squared = direction.length_squared();
+
+    // Solving from the point of the ray closest to the centre keeps the roots
+    // accurate for rays starting far away.
+    let offset = -(ray.origin() - self.center).dot(&direction) / length_squared;
+    let origin = ray.at(offset) - self.center;
+
+    // (|p|^2 + R^2 - r^2)^2 = 4 R^2 (x^2 + z^2), with p = origin + t direction.
+    let major_squared = self.major_radius.powi(2);
+    let a = length_squared;
+    let b = 2.0 * origin.dot(&direction);
+    let c = origin.length_squared() + major_squared - self.minor_radius.powi(2);
+    let ring = 4.0 * major_squared;
+
+    Quartic::new(
+      a * a,
+      2.0 * a * b,
+      (b * b) + (2.0 * a * c) - (ring * (direction.x().powi(2) + direction.z().powi(2))),
+      (2.0 * b * c) - (2.0 * ring * ((origin.x() * direction.x()) + (origin.z() * direction.z()))),
+      (c * c) - (ring * (origin.x().powi(2) + origin.z().powi(2))),
+    )
+    .find_real_roots()
+    .into_iter()
+    .map(|root| root + offset)
+    .collect()
+  }
+
+  fn hit_at(&self, ray: &Ray, time: f64) -> Hit {
+    let local = ray.at(time) - self.center;
+    let around_axis = local.z().atan2(local.x()).rem_euclid(2.0 * PI);
+    let from_axis = local.x().hypot(local.z());
+    let around_tube = local
+      .y()
+      .atan2(from_axis - self.major_radius)
+      .rem_euclid(2.0 * PI);
+
+    Hit::new(time, ray, Arc::clone(&self.material), |_| {
+      // Away from the circle running through the middle of the tube.
+      let ring_point = self.major_radius * Vec3::new(local.x(), 0.0, local.z()).unit();
+      local - ring_point
+    })
+    .with_uv(Vec2::new(
+      around_axis / (2.0 * PI),
+      around_tube / (2.0 * PI),
+    ))
+  }
+}
+
+impl Hittable for Torus {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    self
+      .roots(ray)
+      .into_iter()
+      .find(|time| time.is_within(hittable_range))
+      .map(|time| self.hit_at(ray, time))
+  }
+
+  fn hits(&self, ray: &Ray, hittable_range: Range<f64>) -> Vec<Hit> {
+    self
+      .roots(ray)
+      .into_iter()
+      .filter(|time| time.is_within(hittable_range))
+      .map(|time| self.hit_at(ray, time))
+      .collect()
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    let reach = self.major_radius + self.minor_radius;
+    let extent = Vec3::new(reach, self.minor_radius, reach);
+
+    Some(Aabb::new(self.center - extent, self.center + extent))
+  }
+
+  fn is_solid(&self) -> bool {
+    true
+  }
+}
@@ -0,0 +1,122 @@
+use crate::renderer::core::aabb::Aabb;
+use crate::renderer::core::math::{Mathematical, Range};
+use crate::renderer::core::ray::Ray;
+use crate::renderer::core::vec2::Vec2;
+use crate::renderer::core::vec3::{Point3, Vec3};
+use crate::renderer::materials::material::Material;
+use crate::renderer::samplers::sampler::Sampler;
+use crate::renderer::scene::hittable::{self, Hit, Hittable};
+use std::sync::Arc;
+
+/// Intersects `ray` with the triangle `vertices` using the Möller–Trumbore
+/// algorithm, returning the time of the hit and its barycentric coordinates.
+pub fn intersect(
+  ray: &Ray,
+  [p0, p1, p2]: [Point3; 3],
+  hittable_range: Range<f64>,
+) -> Option<(f64, Vec2<f64>)> {
+  let edge1 = p1 - p0;
+  let edge2 = p2 - p0;
+
+  let p = ray.direction().cross(&edge2);
+  let determinant = edge1.dot(&p);
+
+  // The determinant grows with the size of the triangle and the ray, so rays
+  // parallel to the triangle are judged relative to both.
+  if determinant.abs() <= f64::EPSILON * edge1.length() * p.length() {
+    return None;
+  }
+
+  let inverse_determinant = 1.0 / determinant;
+  let origin_to_p0 = ray.origin() - p0;
+
+  let u = origin_to_p0.dot(&p) * inverse_determinant;
+  if !u.is_within(Range::new(0.0, 1.0)) {
+    return None;
+  }
+
+  let q = origin_to_p0.cross(&edge1);
+  let v = ray.direction().dot(&q) * inverse_determinant;
+  if (v < 0.0) || ((u + v) > 1.0) {
+    return None;
+  }
+
+  let time = edge2.dot(&q) * inverse_determinant;
+
+  time
+    .is_within(hittable_range)
+    .then(|| (time, Vec2::new(u, v)))
+}
+
+/// The unnormalised normal of the triangle `vertices`, whose length is twice
+/// the triangle's area.
+pub fn normal([p0, p1, p2]: [Point3; 3]) -> Vec3 {
+  (p1 - p0).cross(&(p2 - p0))
+}
+
+pub fn area(vertices: [Point3; 3]) -> f64 {
+  normal(vertices).length() / 2.0
+}
+
+/// Picks a point uniformly over the area of the triangle `vertices`.
+pub fn sample([p0, p1, p2]: [Point3; 3], sampler: &mut dyn Sampler) -> Point3 {
+  let (s, t) = sampler.get_2d();
+  let root = s.sqrt();
+  let u = root * t;
+  let v = root - u;
+
+  p0 + (u * (p1 - p0)) + (v * (p2 - p0))
+}
+
+pub struct Triangle {
+  vertices: [Point3; 3],
+  material: Arc<dyn Material>,
+}
+
+impl Triangle {
+  pub fn new(vertices: [Point3; 3], material: Arc<dyn Material>) -> Self {
+    Self { vertices, material }
+  }
+}
+
+impl Hittable for Triangle {
+  fn hit(&self, ray: &Ray, hittable_range: Range<f64>) -> Option<Hit> {
+    intersect(ray, self.vertices, hittable_range).map(|(time, barycentric)| {
+      Hit::new(time, ray, Arc::clone(&self.material), |_| {
+        normal(self.vertices)
+      })
+      .with_uv(barycentric)
+      .with_barycentric(barycentric)
+    })
+  }
+
+  fn bounding_box(&self) -> Option<Aabb> {
+    Some(Aabb::from_points(&self.vertices))
+  }
+
+  fn sample_point(&self, origin: &Point3, sampler: &mut dyn Sampler) -> Option<(Point3, f64)> {
+    let point = sample(self.vertices, sampler);
+    let pdf = hittable::area_to_solid_angle(
+      1.0 / area(self.vertices),
+      origin,
+      &point,
+      &normal(self.vertices),
+    );
+
+    Some((point, pdf))
+  }
+
+  fn pdf(&self, origin: &Point3, direction: &Vec3) -> f64 {
+    let ray = Ray::new(*origin, *direction);
+
+    match intersect(&ray, self.vertices, Range::new(0.001, f64::INFINITY)) {
+      Some((time, _)) => hittable::area_to_solid_angle(
+        1.0 / area(self.vertices),
+        origin,
+        &ray.at(time),
+        &normal(self.vertices),
+      ),
+      None => 0.0,
+    }
+  }
+}
@@ -0,0 +1,67 @@
+use crate::renderer::core::vec2::Vec2;
+use crate::renderer::film::Film;
+use crate::renderer::render::{self, RenderParams, Scene};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+
+/// The width and height of the tiles handed to threads, in pixels.
+const TILE_SIZE: usize = 16;
+
+/// Samples every pixel of `film` until it holds `samples` samples or converges,
+/// on `threads` threads which take tiles from a shared queue until none are
+/// left. Threads which finish cheap tiles go on to help with the rest, so
+/// uneven scenes keep every thread busy.
+///
+/// No more tiles are started once `deadline` passes, so that progress can be
+/// saved. The film's sample count is then left as it was, and rendering the
+/// same `samples` again picks up from the tiles left.
+pub fn render(
+  params: &RenderParams,
+  scene: &Scene,
+  film: &mut Film,
+  samples: usize,
+  threads: usize,
+  deadline: Option<Instant>,
+) {
+  let tiles = film.tiles(TILE_SIZE);
+  let tile_count = tiles.len();
+
+  let film = Mutex::new(film);
+  let queue = Mutex::new(tiles.into_iter());
+  let finished = AtomicUsize::new(0);
+
+  thread::scope(|scope| {
+    for _ in 0..threads {
+      scope.spawn(|| loop {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+          break;
+        }
+        // Only hold the queue's lock while taking a tile.
+        let Some(mut tile) = queue.lock().unwrap().next() else {
+          break;
+        };
+
+        for (pixel, stats) in tile.pixels_mut() {
+          // Film rows count from the top, but the camera's from the bottom.
+          let pixel = Vec2::new(pixel.x(), params.last_pixel().y() - pixel.y());
+          render::render_pixel(params, scene, pixel, stats, samples);
+        }
+
+        film.lock().unwrap().add_tile(&tile);
+
+        let finished = finished.fetch_add(1, Ordering::Relaxed) + 1;
+        eprint!(
+          "\rSamples {samples}/{}, tiles remaining: {}  ",
+          params.samples_per_pixel,
+          tile_count - finished
+        );
+      });
+    }
+  });
+
+  if finished.into_inner() == tile_count {
+    film.into_inner().unwrap().set_samples(samples);
+  }
+}
@@ -0,0 +1,28 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// An error in a text file, located by the line it occurred on.
+#[derive(Debug)]
+pub struct ParseError {
+  file: String,
+  line: usize,
+  message: String,
+}
+
+impl ParseError {
+  pub fn new(file: impl Into<String>, line: usize, message: impl Into<String>) -> Self {
+    Self {
+      file: file.into(),
+      line,
+      message: message.into(),
+    }
+  }
+}
+
+impl Display for ParseError {
+  fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+    write!(formatter, "{}:{}: {}", self.file, self.line, self.message)
+  }
+}
+
+impl Error for ParseError {}